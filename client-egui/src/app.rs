@@ -1,11 +1,20 @@
 use eframe::egui;
-use eframe::epaint::Color32;
-use egui::Visuals;
 
 use tsurust_common::board::*;
 use tsurust_common::game::Game;
 use crate::board_renderer::BoardRenderer;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::board_export::save_board_svg;
+use crate::history_scrubber::{history_scrubber, shown_moves};
+use crate::log_console::LogConsole;
+use crate::move_history::move_history;
+use crate::theme::ThemeKind;
+use crate::unseen_tiles::unseen_tiles_window;
 use crate::tile_button::TileButton;
+use crate::tile_tooltip::tile_tooltip;
+
+/// The player sitting at this client, until there's a lobby that assigns seats
+const LOCAL_PLAYER: PlayerID = 1;
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -16,11 +25,24 @@ pub struct TemplateApp {
     tile: Tile,
     #[serde(skip)]
     game: tsurust_common::game::Game,
+    log_console: LogConsole,
+    theme: ThemeKind,
+    show_move_history: bool,
+    show_unseen_tiles: bool,
+    /// House rule: once per game a tile may be mirrored instead of rotated
+    flip_variant: bool,
+    #[serde(skip)]
+    highlighted_move: Option<usize>,
+    /// Set while looking back at an earlier board state instead of the live one
+    #[serde(skip)]
+    viewing_move: Option<usize>,
+    #[serde(skip)]
+    export_status: Option<String>,
 }
 
 impl Default for TemplateApp {
     fn default() -> Self {
-        let mut game = Game::new(vec![Player{alive: true, id: 1, pos: PlayerPos::new(0, 0, 0)}]);
+        let mut game = Game::new(vec![Player{alive: true, id: LOCAL_PLAYER, pos: PlayerPos::valid_spawn_positions()[0]}]);
         let random_tiles = game.deck.take_up_to(36);
 
         random_tiles.iter()
@@ -28,15 +50,23 @@ impl Default for TemplateApp {
             .for_each(|(i, tile)| {
                 let (row, col) = (i/6, i % 6);
                 let coord = CellCoord {row, col};
-                let tile = tile.clone();
-                game.perform_move(Move {tile, cell: coord ,player_id: 1})
+                let tile = *tile;
+                game.perform_move(Move {tile, cell: coord ,player_id: LOCAL_PLAYER})
             });
 
 
         Self {
             label: "Hello Year of the Dragon of Wood - Hello Tsurust!".to_owned(),
             tile: Tile::new([seg(0, 2), seg(1, 4), seg(3, 5), seg(6, 7)]),
-            game
+            game,
+            log_console: LogConsole::default(),
+            theme: ThemeKind::default(),
+            show_move_history: true,
+            show_unseen_tiles: false,
+            flip_variant: false,
+            highlighted_move: None,
+            viewing_move: None,
+            export_status: None,
         }
     }
 }
@@ -47,6 +77,7 @@ impl TemplateApp {
 
 
         if let Some(storage) = cc.storage {
+            tracing::info!("restoring app state");
             return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
         }
         Default::default()
@@ -55,8 +86,15 @@ impl TemplateApp {
 
 impl eframe::App for TemplateApp {
     /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &eframe::egui::Context, frame: &mut eframe::Frame) {
-        let Self { label, tile, game } = self;
+    fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
+        let Self {
+            tile, game, log_console, theme, show_move_history, show_unseen_tiles, flip_variant, highlighted_move, viewing_move,
+            export_status, ..
+        } = self;
+        let palette = theme.theme();
+        if ctx.style().visuals.dark_mode != palette.dark_mode {
+            ctx.set_visuals(palette.visuals());
+        }
 
         egui::TopBottomPanel::top("top_panel")
             .resizable(true)
@@ -65,9 +103,38 @@ impl eframe::App for TemplateApp {
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     ui.vertical(|ui| {
                         ui.heading("🐉🐉[server: local - room 01 - room host: alyosha] 🐉🐉");
-                        ui.heading("🐉🐉 [turn 1 (alyosha) - tiles left: 0 - ] 🐉🐉");
+                        ui.heading(format!("🐉🐉 [turn 1 (alyosha) - tiles left: {} - ] 🐉🐉", game.deck.len()));
                         ui.heading("(alyosha) [Automat] [Pig] [Rooster] [Dragon]");
                     });
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_label("Theme")
+                            .selected_text(palette.name)
+                            .show_ui(ui, |ui| {
+                                for kind in ThemeKind::ALL {
+                                    ui.selectable_value(theme, kind, kind.theme().name);
+                                }
+                            });
+                        ui.toggle_value(show_move_history, "📜 Moves");
+                        ui.toggle_value(show_unseen_tiles, "🔍 Unseen tiles");
+                        ui.toggle_value(flip_variant, "⇋ Flip variant")
+                            .on_hover_text("Once per game, mirror the tile by clicking its middle");
+                        ui.toggle_value(&mut log_console.open, "🐛 Debug console");
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button("🖼 Export SVG").clicked() {
+                            let shown = shown_moves(*viewing_move, game.board.history.len());
+                            *export_status = Some(match save_board_svg(&game.board.history[..shown], palette) {
+                                Ok(path) => format!("Saved {}", path.display()),
+                                Err(e) => {
+                                    tracing::error!("board export failed: {}", e);
+                                    format!("Export failed: {}", e)
+                                }
+                            });
+                        }
+                        if let Some(status) = export_status {
+                            ui.label(status.as_str());
+                        }
+                    });
                 });
             });
 
@@ -77,15 +144,35 @@ impl eframe::App for TemplateApp {
             .show(ctx, |ui| {
                 ui.horizontal_centered(|ui| {
                     ui.add_space(20.);
-                    ui.add(TileButton::new(tile));
+                    let mut button = TileButton::new(tile, palette);
+                    if *flip_variant {
+                        button = button.flip_for(game, LOCAL_PLAYER);
+                    }
+                    ui.add_enabled(viewing_move.is_none(), button)
+                        .on_hover_ui(|ui| tile_tooltip(ui, tile, palette));
                 });
             });
 
+        egui::SidePanel::right("move_history")
+            .resizable(true)
+            .show_animated(ctx, *show_move_history, |ui| {
+                move_history(ui, &game.board.history, highlighted_move, palette);
+            });
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
-                ui.add(BoardRenderer::new(&mut self.game.board.history, &mut self.game.players));
+                let shown_moves = history_scrubber(ui, viewing_move, game.board.history.len(), palette);
+                let shown_history = &game.board.history[..shown_moves];
+                let highlighted_cell = highlighted_move.and_then(|i| shown_history.get(i)).map(|mov| mov.cell);
+                ui.add(
+                    BoardRenderer::new(shown_history, &game.players, palette)
+                        .highlighted(highlighted_cell)
+                );
             });
         });
+
+        unseen_tiles_window(ctx, show_unseen_tiles, &game.board.history, std::slice::from_ref(tile), palette);
+        log_console.show(ctx, palette);
     }
 
     /// Called by the framework to save state before shutdown.
@@ -0,0 +1,113 @@
+use std::fmt::Write;
+
+use eframe::egui::Color32;
+use tsurust_common::board::*;
+
+use crate::rendering::segment_path;
+use crate::theme::Theme;
+
+/// Size of a cell in the exported image, in SVG user units
+const CELL_LENGTH: f32 = 110.;
+
+/// Renders the board with the given moves placed on it as a standalone SVG document,
+/// using the same segment geometry as the on-screen `BoardRenderer`.
+pub fn board_svg(history: &[Move], theme: &Theme) -> String {
+    let board_length = CELL_LENGTH * BOARD_LENGTH as f32;
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+        board_length
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <rect width="{0}" height="{0}" fill="{1}" stroke="{2}" stroke-width="4"/>"#,
+        board_length,
+        svg_color(theme.board_background, theme.board_background),
+        svg_color(theme.board_border, theme.board_background)
+    );
+
+    let _ = writeln!(
+        svg,
+        r#"  <g fill="none" stroke="{}" stroke-width="2">"#,
+        svg_color(theme.tile_path, theme.board_background)
+    );
+    for mov in history {
+        let (x, y) = (mov.cell.col as f32 * CELL_LENGTH, mov.cell.row as f32 * CELL_LENGTH);
+        for &segment in &mov.tile.segments {
+            // segment paths are laid out in a 3x3 space, scale that to the cell
+            let points: Vec<String> = segment_path(segment)
+                .iter()
+                .map(|p| format!("{},{}", x + p.x * CELL_LENGTH / 3., y + p.y * CELL_LENGTH / 3.))
+                .collect();
+            let _ = writeln!(svg, r#"    <polyline points="{}"/>"#, points.join(" "));
+        }
+    }
+    svg.push_str("  </g>\n</svg>\n");
+
+    svg
+}
+
+/// Theme colors are premultiplied and may be translucent; flatten them onto the board background
+/// so the image looks the same as the board does on screen.
+fn svg_color(color: Color32, background: Color32) -> String {
+    let transparency = 1. - color.a() as f32 / 255.;
+    let blend = |channel: u8, under: u8| (channel as f32 + under as f32 * transparency).min(255.) as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        blend(color.r(), background.r()),
+        blend(color.g(), background.g()),
+        blend(color.b(), background.b())
+    )
+}
+
+/// Writes the board as an SVG file in the working directory, returning the file name.
+pub fn save_board_svg(history: &[Move], theme: &Theme) -> std::io::Result<std::path::PathBuf> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let path = std::path::PathBuf::from(format!("tsurust-board-{}.svg", timestamp));
+
+    std::fs::write(&path, board_svg(history, theme))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::Color32;
+    use tsurust_common::board::*;
+
+    use super::{board_svg, svg_color, CELL_LENGTH};
+    use crate::theme::Theme;
+
+    #[test]
+    fn translucent_colors_are_flattened_onto_the_background() {
+        assert_eq!(svg_color(Theme::DARK.board_border, Color32::BLACK), "#c8327d");
+        assert_eq!(svg_color(Color32::from_rgba_premultiplied(100, 0, 0, 128), Color32::from_gray(200)), "#c76363");
+        assert_eq!(svg_color(Color32::from_rgb(1, 2, 3), Color32::WHITE), "#010203");
+    }
+
+    #[test]
+    fn each_move_is_drawn_as_four_polylines_inside_the_board() {
+        let tile = Tile::new([seg(0, 5), seg(1, 4), seg(2, 7), seg(3, 6)]);
+        let corner = BOARD_LENGTH - 1;
+        let svg = board_svg(&[Move { tile, cell: CellCoord { row: corner, col: corner }, player_id: 1 }], &Theme::DARK);
+
+        let board_length = CELL_LENGTH * BOARD_LENGTH as f32;
+        assert!(svg.contains(&format!(r#"viewBox="0 0 {0} {0}""#, board_length)));
+
+        let polylines: Vec<&str> = svg.lines().filter(|line| line.contains("<polyline")).collect();
+        assert_eq!(polylines.len(), 4);
+        let cell_min = corner as f32 * CELL_LENGTH;
+        for polyline in polylines {
+            let points = polyline.split('"').nth(1).unwrap();
+            for point in points.split(' ') {
+                let (x, y) = point.split_once(',').unwrap();
+                let (x, y): (f32, f32) = (x.parse().unwrap(), y.parse().unwrap());
+                assert!((cell_min..=board_length).contains(&x), "{}", polyline);
+                assert!((cell_min..=board_length).contains(&y), "{}", polyline);
+            }
+        }
+    }
+}
@@ -1,68 +1,140 @@
-use eframe::egui::{vec2, Frame, Rect, Sense, Widget, Response, Ui};
-use eframe::emath::Vec2;
-use eframe::epaint::{Color32, Stroke};
+use std::sync::Arc;
+
+use eframe::egui::{vec2, Color32, Frame, Id, Rect, Sense, Shape, Widget, Response, Ui};
+use eframe::epaint::{Mesh, Stroke, Tessellator};
 use egui::Pos2;
 use tsurust_common::board::*;
 
-use crate::rendering::{paint_tile, PINK};
+use crate::rendering::{tile_lines, tile_path_stroke};
+use crate::theme::Theme;
+
+/// Cells are drawn at this size when there's room for it, and shrink down to `MIN_TILE_LENGTH` on small screens
+const MAX_TILE_LENGTH: f32 = 110.;
+const MIN_TILE_LENGTH: f32 = 40.;
 
-const TILE_SIZE: Vec2 = Vec2::new(110., 110.);
 pub struct BoardRenderer<'a> {
-    history: &'a mut Vec<Move>, //to-do, alias this type, do these folks need to be mutable?
-    players: &'a mut Vec<Player>
+    history: &'a [Move],
+    players: &'a [Player],
+    theme: &'a Theme,
+    highlighted: Option<CellCoord>,
 }
 
 impl <'a> BoardRenderer<'a> {
-    pub(crate) fn new(history: &'a mut Vec<Move>, players: &'a mut Vec<Player>) -> Self {
-        Self { history, players }
+    pub(crate) fn new(history: &'a [Move], players: &'a [Player], theme: &'a Theme) -> Self {
+        Self { history, players, theme, highlighted: None }
+    }
+
+    /// Outline the given cell, e.g. the one picked in the move history
+    pub(crate) fn highlighted(mut self, cell: Option<CellCoord>) -> Self {
+        self.highlighted = cell;
+        self
     }
 }
 
 impl Widget for BoardRenderer<'_> {
     fn ui(self, ui: &mut Ui) -> Response {
-        let (rows, cols) = (6.,6.);
-        let tile_length: f32 = 110.0;
+        let (rows, cols) = (BOARD_LENGTH as f32, BOARD_LENGTH as f32);
+        let available = ui.available_size();
+        let tile_length = (available.x / cols).min(available.y / rows).clamp(MIN_TILE_LENGTH, MAX_TILE_LENGTH);
 
         let (board_rect, response) = ui.allocate_at_least(
-            vec2(rows * tile_length, cols * tile_length),
+            vec2(cols * tile_length, rows * tile_length),
             Sense::click().union(Sense::hover())
         );
         
-        background(ui, board_rect);
+        background(ui, board_rect, self.theme);
 
+        let cache_id = ui.id().with("board_tiles");
         ui.vertical_centered(|ui| {
-            tiles(ui, self.history, board_rect);
+            tiles(ui, cache_id, self.history, board_rect, tile_length, self.theme);
         });
 
+        if let Some(cell) = self.highlighted {
+            let rect = rect_at_coord(cell, board_rect, tile_length);
+            ui.painter().rect_stroke(rect.shrink(1.), 0.5, Stroke::new(3.0, self.theme.tile_hover));
+        }
+
         for player in self.players {
-            let cell_rect = rect_at_coord(player.pos.cell, board_rect);
-            // cell_rect - TILE_SIZE * Vec2::new(0.5, 0.5);
+            let _cell_rect = rect_at_coord(player.pos.cell, board_rect, tile_length);
+            // cell_rect - tile_length * Vec2::new(0.5, 0.5);
         }
 
         response
     }
 }
 
-fn rect_at_coord(cell_coord: CellCoord, board_rect: Rect) -> Rect {
-    let pos = Pos2::new(cell_coord.col as f32 * 110., cell_coord.row as f32 * 110.) + board_rect.min.to_vec2();
-    Rect::from_min_size(pos, TILE_SIZE)
+fn rect_at_coord(cell_coord: CellCoord, board_rect: Rect, tile_length: f32) -> Rect {
+    let pos = Pos2::new(cell_coord.col as f32 * tile_length, cell_coord.row as f32 * tile_length) + board_rect.min.to_vec2();
+    Rect::from_min_size(pos, vec2(tile_length, tile_length))
 }
 
-fn tiles(ui: &mut Ui, history: &Vec<Move>, board_rect: Rect) {
-    Frame::canvas(ui.style()).show(ui, |ui| {
-        let painter = ui.painter();
+/// Everything the tiles' mesh depends on: while none of it changes the cached mesh is reused as is.
+struct TilesKey {
+    history: Vec<Move>,
+    board_rect: Rect,
+    tile_length: f32,
+    color: Color32,
+    pixels_per_point: f32,
+}
 
-        for mov in history {
-            let rect = rect_at_coord(mov.cell, board_rect);
-            paint_tile(&mov.tile, rect, painter);
-        }
+impl TilesKey {
+    /// Compares against the current frame's values without copying the history.
+    fn matches(&self, history: &[Move], board_rect: Rect, tile_length: f32, color: Color32, pixels_per_point: f32) -> bool {
+        self.history == history
+            && self.board_rect == board_rect
+            && self.tile_length == tile_length
+            && self.color == color
+            && self.pixels_per_point == pixels_per_point
+    }
+}
+
+/// Stored behind an `Arc` so looking it up every frame doesn't copy the key.
+struct CachedTiles {
+    key: TilesKey,
+    mesh: Mesh,
+}
+
+/// Paints every placed tile. The board only changes when a move is made (or the window is resized),
+/// so the tiles are tessellated into a single mesh once and that mesh is painted on every other frame.
+fn tiles(ui: &mut Ui, cache_id: Id, history: &[Move], board_rect: Rect, tile_length: f32, theme: &Theme) {
+    let ctx = ui.ctx().clone();
+    let pixels_per_point = ctx.pixels_per_point();
+
+    let cached = ctx.data(|data| data.get_temp::<Arc<CachedTiles>>(cache_id))
+        .filter(|cached| cached.key.matches(history, board_rect, tile_length, theme.tile_path, pixels_per_point));
+    let cached = cached.unwrap_or_else(|| {
+        let key = TilesKey { history: history.to_vec(), board_rect, tile_length, color: theme.tile_path, pixels_per_point };
+        let mesh = tessellate_tiles(&ctx, history, board_rect, tile_length, theme);
+        let cached = Arc::new(CachedTiles { key, mesh });
+        ctx.data_mut(|data| data.insert_temp(cache_id, cached.clone()));
+        cached
     });
 
+    Frame::canvas(ui.style()).show(ui, |ui| {
+        // Shapes are consumed by the painter, so the mesh's buffers still get copied once per frame
+        ui.painter().add(Shape::mesh(cached.mesh.clone()));
+    });
+}
+
+fn tessellate_tiles(ctx: &egui::Context, history: &[Move], board_rect: Rect, tile_length: f32, theme: &Theme) -> Mesh {
+    let options = ctx.tessellation_options(|options| *options);
+    let font_tex_size = ctx.fonts(|fonts| fonts.font_image_size());
+    let mut tessellator = Tessellator::new(ctx.pixels_per_point(), options, font_tex_size, Vec::new());
+
+    let stroke = tile_path_stroke(theme);
+    let mut mesh = Mesh::default();
+    for mov in history {
+        let rect = rect_at_coord(mov.cell, board_rect, tile_length);
+        for points in tile_lines(&mov.tile, rect) {
+            tessellator.tessellate_line(points, stroke, &mut mesh);
+        }
+    }
+    mesh
 }
 
-fn background(ui: &mut Ui, rect: Rect) {
-    ui.painter().rect_filled(rect, 0.6, Color32::BLACK);
-    ui.painter().rect_stroke(rect, 0.5, Stroke::new(4.0, PINK));
+fn background(ui: &mut Ui, rect: Rect, theme: &Theme) {
+    ui.painter().rect_filled(rect, 0.6, theme.board_background);
+    ui.painter().rect_stroke(rect, 0.5, Stroke::new(4.0, theme.board_border));
 
     //crate::backgr_render::draw_yin_yang(ui, 120.);
 }
\ No newline at end of file
@@ -0,0 +1,34 @@
+use eframe::egui;
+
+use crate::theme::Theme;
+
+/// Slider for looking back at earlier board states during a game. `viewing` is `None` while following live play,
+/// or the number of moves to show while looking back. Returns how many moves of the history should be drawn.
+pub fn history_scrubber(ui: &mut egui::Ui, viewing: &mut Option<usize>, live_moves: usize, theme: &Theme) -> usize {
+    let mut shown = shown_moves(*viewing, live_moves);
+
+    ui.horizontal(|ui| {
+        ui.label("History");
+        if ui.add(egui::Slider::new(&mut shown, 0..=live_moves)).changed() {
+            *viewing = (shown < live_moves).then_some(shown);
+        }
+        if ui.add_enabled(viewing.is_some(), egui::Button::new("⏭ Live")).clicked() {
+            *viewing = None;
+            shown = live_moves;
+        }
+    });
+
+    if viewing.is_some() {
+        ui.colored_label(
+            theme.tile_hover,
+            format!("Viewing the board after move {} of {}. Return to live play to make a move.", shown, live_moves),
+        );
+    }
+
+    shown
+}
+
+/// How many moves are on display given the scrubber state
+pub fn shown_moves(viewing: Option<usize>, live_moves: usize) -> usize {
+    viewing.unwrap_or(live_moves).min(live_moves)
+}
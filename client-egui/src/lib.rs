@@ -3,10 +3,21 @@
 mod app;
 mod rendering;
 mod tile_button;
+mod tile_thumbnail;
+mod tile_tooltip;
+#[cfg(not(target_arch = "wasm32"))]
+mod board_export;
 mod board_renderer;
+mod history_scrubber;
+mod log_console;
+mod move_history;
+mod theme;
+mod unseen_tiles;
+#[allow(unused)] // scratch module, not wired up yet
 mod backgr_render;
 
 pub use app::TemplateApp;
+pub use log_console::ConsoleLayer;
 
 // ----------------------------------------------------------------------------
 // When compiling for web:
@@ -24,8 +35,12 @@ pub fn start(canvas_id: &str) -> Result<(), eframe::wasm_bindgen::JsValue> {
     // Make sure panics are logged using `console.error`.
     console_error_panic_hook::set_once();
 
-    // Redirect tracing to console.log and friends:
-    tracing_wasm::set_as_global_default();
+    // Redirect tracing to console.log and friends, and to the in-app debug console:
+    use tracing_subscriber::prelude::*;
+    tracing_subscriber::registry()
+        .with(tracing_wasm::WASMLayer::new(tracing_wasm::WASMLayerConfig::default()))
+        .with(ConsoleLayer.with_filter(tracing_subscriber::filter::LevelFilter::DEBUG))
+        .init();
 
     eframe::start_web(canvas_id, Box::new(|cc| Box::new(TemplateApp::new(cc))))
 }
@@ -0,0 +1,126 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Write};
+use std::sync::Mutex;
+
+use eframe::egui::{self, Color32, RichText};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+use crate::theme::Theme;
+
+/// How many log lines the console keeps around
+const CAPACITY: usize = 500;
+
+static RECORDS: Mutex<VecDeque<LogRecord>> = Mutex::new(VecDeque::new());
+
+struct LogRecord {
+    level: Level,
+    target: String,
+    message: String,
+}
+
+/// A `tracing` layer that copies every event it sees into the console's ring buffer.
+pub struct ConsoleLayer;
+
+impl<S: Subscriber> Layer<S> for ConsoleLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut message = String::new();
+        event.record(&mut MessageVisitor(&mut message));
+
+        let record = LogRecord {
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_owned(),
+            message,
+        };
+
+        if let Ok(mut records) = RECORDS.lock() {
+            if records.len() == CAPACITY {
+                records.pop_front();
+            }
+            records.push_back(record);
+        }
+    }
+}
+
+struct MessageVisitor<'a>(&'a mut String);
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Collapsible window showing the most recent log lines, so problems can be reported without dev tools.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct LogConsole {
+    pub open: bool,
+    #[serde(skip)]
+    max_level: Level,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self { open: false, max_level: Level::INFO }
+    }
+}
+
+impl LogConsole {
+    pub fn show(&mut self, ctx: &egui::Context, theme: &Theme) {
+        let Self { open, max_level } = self;
+
+        egui::Window::new("Debug console")
+            .open(open)
+            .default_size([600., 300.])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Show up to:");
+                    for level in [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE] {
+                        ui.selectable_value(max_level, level, level.as_str());
+                    }
+                    if ui.button("Clear").clicked() {
+                        if let Ok(mut records) = RECORDS.lock() {
+                            records.clear();
+                        }
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        // Copied out so the lock isn't held while laying out: egui itself logs (e.g. when the font
+                        // atlas fills up), and that would land back in `ConsoleLayer` on this same thread
+                        let lines: Vec<(Level, String)> = {
+                            let Ok(records) = RECORDS.lock() else { return };
+                            // Level's ordering is by verbosity: ERROR is the "smallest" one
+                            records.iter()
+                                .filter(|record| record.level <= *max_level)
+                                .map(|record| {
+                                    (record.level, format!("{:>5} {}: {}", record.level, record.target, record.message))
+                                })
+                                .collect()
+                        };
+                        for (level, line) in lines {
+                            ui.label(RichText::new(line).monospace().color(level_color(level, theme)));
+                        }
+                    });
+            });
+    }
+}
+
+fn level_color(level: Level, theme: &Theme) -> Color32 {
+    match level {
+        Level::ERROR => theme.log_error,
+        Level::WARN => theme.log_warn,
+        Level::INFO => theme.log_info,
+        _ => theme.log_debug,
+    }
+}
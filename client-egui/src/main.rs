@@ -3,9 +3,20 @@
 
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
-fn main() {
-    // Log to stdout (if run with `RUST_LOG=debug`).
-    tracing_subscriber::fmt::init();
+fn main() -> eframe::Result<()> {
+    use tracing_subscriber::filter::{LevelFilter, Targets};
+    use tracing_subscriber::prelude::*;
+
+    // Log to stdout (more if run with e.g. `RUST_LOG=debug`), and keep recent messages around for the
+    // in-app debug console, which has its own level picker
+    let stdout_filter = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|var| var.parse::<Targets>().map_err(|e| eprintln!("Ignoring `RUST_LOG={}`: {}", var, e)).ok())
+        .unwrap_or_else(|| Targets::new().with_default(LevelFilter::INFO));
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(stdout_filter))
+        .with(client_egui::ConsoleLayer.with_filter(LevelFilter::DEBUG))
+        .init();
 
     let native_options = eframe::NativeOptions::default();
     
@@ -13,5 +24,5 @@ fn main() {
         "eframe template",
         native_options,
         Box::new(|cc| Box::new(client_egui::TemplateApp::new(cc))),
-    );
+    )
 }
@@ -0,0 +1,42 @@
+use eframe::egui;
+
+use tsurust_common::board::*;
+use tsurust_common::notation::encode_game;
+
+use crate::theme::Theme;
+use crate::tile_thumbnail::TileThumbnail;
+use crate::tile_tooltip::tile_tooltip;
+
+/// Lists every move played so far, newest at the bottom. Clicking a move selects it so the board can highlight
+/// where it was placed; clicking it again clears the selection.
+pub fn move_history(ui: &mut egui::Ui, history: &[Move], highlighted: &mut Option<usize>, theme: &Theme) {
+    ui.horizontal(|ui| {
+        ui.heading("Moves");
+        if ui.button("📋").on_hover_text("Copy the game in TSN").clicked() {
+            match encode_game(history) {
+                Ok(notation) => ui.output_mut(|output| output.copied_text = notation),
+                Err(e) => tracing::error!("can't write the game in TSN: {}", e),
+            }
+        }
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .stick_to_bottom(true)
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (i, mov) in history.iter().enumerate() {
+                let selected = *highlighted == Some(i);
+                let label = ui.horizontal(|ui| {
+                    ui.add(TileThumbnail::new(&mov.tile, theme)).on_hover_ui(|ui| tile_tooltip(ui, &mov.tile, theme));
+                    let notation = mov.to_notation()
+                        .unwrap_or_else(|_| format!("P{} ({}, {})", mov.player_id, mov.cell.row, mov.cell.col));
+                    ui.selectable_label(selected, format!("{:>2}. {}", i + 1, notation))
+                }).inner;
+
+                if label.clicked() {
+                    *highlighted = if selected { None } else { Some(i) };
+                }
+            }
+        });
+}
@@ -1,59 +1,77 @@
 use eframe::egui::{
-    emath::RectTransform, pos2, Align2, Color32, FontId, Painter, Pos2, Rect, Stroke,
+    emath::RectTransform, pos2, Align2, FontId, Painter, Pos2, Rect, Stroke,
 };
-use tsurust_common::board::{Board, Segment, Tile, TileEndpoint};
+use tsurust_common::board::{Segment, Tile, TileEndpoint};
 
-pub const TRANSPARENT_WHITE: Color32 = Color32::from_rgba_premultiplied(255, 255, 255, 191);
-pub const TRANSPARENT_GOLD: Color32 = Color32::from_rgba_premultiplied(255, 215, 0, 191);
-pub const PINK: Color32 = Color32::from_rgba_premultiplied(200, 50, 125, 44);
-pub fn paint_board(board: &Board) {}
+use crate::theme::Theme;
 
-pub fn paint_tile(tile: &Tile, rect: Rect, painter: &Painter) {
+pub fn paint_tile(tile: &Tile, rect: Rect, painter: &Painter, theme: &Theme) {
+    let stroke = tile_path_stroke(theme);
+    for points in tile_lines(tile, rect) {
+        painter.line_segment(points, stroke);
+    }
+}
+
+pub fn tile_path_stroke(theme: &Theme) -> Stroke {
+    Stroke::new(2., theme.tile_path)
+}
+
+/// The straight lines a tile is drawn with when it fills `rect`, in screen space.
+pub fn tile_lines(tile: &Tile, rect: Rect) -> impl Iterator<Item = [Pos2; 2]> + '_ {
     let to_screen = tile_to_screen_transform(rect);
-    let stroke = Stroke::new(2., TRANSPARENT_WHITE);
 
-    tile.segments
-        .iter()
-        .for_each(|&Segment { a: from, b: to }| {
-            let start_chunk = segment_tail(from);
-            let end_chunk = segment_tail(to);
-            let middle_chunk = [start_chunk[1], end_chunk[1]];
+    tile.segments.iter().flat_map(move |&segment| {
+        let path = segment_path(segment).map(|point| to_screen.transform_pos(point));
+        [[path[0], path[1]], [path[1], path[2]], [path[2], path[3]]]
+    })
+}
 
-            [start_chunk, middle_chunk, end_chunk]
-                .iter()
-                .for_each(|line| {
-                    let points = line.map(|point| to_screen.transform_pos(point));
-                    painter.line_segment(points, stroke);
-                });
-        });
+/// The path a segment draws inside a tile, as a polyline in the 3x3 tile space used by `tile_to_screen_transform`:
+/// a short tail in from each entry point, joined by a straight line.
+pub fn segment_path(Segment { a: from, b: to }: Segment) -> [Pos2; 4] {
+    let start_chunk = segment_tail(from);
+    let end_chunk = segment_tail(to);
+    [start_chunk[0], start_chunk[1], end_chunk[1], end_chunk[0]]
 }
 
-pub fn paint_tile_button_hoverlay(rect: Rect, painter: &Painter) {
+pub fn paint_tile_button_hoverlay(rect: Rect, painter: &Painter, theme: &Theme, flippable: bool) {
     let to_screen = tile_to_screen_transform(rect);
     let font_size = rect.size().x / 7.;
-    painter.rect_stroke(rect, 0.5, Stroke::new(2.0, TRANSPARENT_GOLD));
+    painter.rect_stroke(rect, 0.5, Stroke::new(2.0, theme.tile_hover));
 
     let radius = font_size * 0.86;
     let rotate_cw_pos = to_screen.transform_pos(pos2(3., 1.5));
     let rotate_ccw_pos = to_screen.transform_pos(pos2(0., 1.5));
 
-    painter.circle_filled(rotate_cw_pos, radius, Color32::BLACK);
-    painter.circle_filled(rotate_ccw_pos, radius, Color32::BLACK);
+    painter.circle_filled(rotate_cw_pos, radius, theme.rotate_button_background);
+    painter.circle_filled(rotate_ccw_pos, radius, theme.rotate_button_background);
 
     painter.text(
         rotate_cw_pos,
         Align2::CENTER_CENTER,
         "⟳",
         FontId::monospace(font_size),
-        TRANSPARENT_WHITE,
+        theme.rotate_button_text,
     );
     painter.text(
         rotate_ccw_pos,
         Align2::CENTER_CENTER,
         "⟲",
         FontId::monospace(font_size),
-        TRANSPARENT_WHITE,
+        theme.rotate_button_text,
     );
+
+    if flippable {
+        let flip_pos = to_screen.transform_pos(pos2(1.5, 1.5));
+        painter.circle_filled(flip_pos, radius, theme.rotate_button_background);
+        painter.text(
+            flip_pos,
+            Align2::CENTER_CENTER,
+            "⇋",
+            FontId::monospace(font_size),
+            theme.rotate_button_text,
+        );
+    }
 }
 
 pub fn tile_to_screen_transform(rect: Rect) -> RectTransform {
@@ -65,6 +83,11 @@ pub fn tile_to_screen_transform(rect: Rect) -> RectTransform {
     )
 }
 
+/// Where an endpoint sits on a tile's border, in the 3x3 tile space.
+pub fn endpoint_position(endpoint: TileEndpoint) -> Pos2 {
+    segment_tail(endpoint)[0]
+}
+
 fn segment_tail(index: TileEndpoint) -> [Pos2; 2] {
     let (a, b) = match index {
         0 => ((1., 3.), (1., 2.5)),
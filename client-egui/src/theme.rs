@@ -0,0 +1,72 @@
+use eframe::egui::{Color32, Visuals};
+
+/// Which `Theme` the user picked. This is what gets persisted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ThemeKind {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// All the colors used to paint the board and tiles.
+pub struct Theme {
+    pub name: &'static str,
+    pub dark_mode: bool,
+    pub board_background: Color32,
+    pub board_border: Color32,
+    pub tile_path: Color32,
+    pub tile_hover: Color32,
+    pub rotate_button_background: Color32,
+    pub rotate_button_text: Color32,
+    pub log_error: Color32,
+    pub log_warn: Color32,
+    pub log_info: Color32,
+    pub log_debug: Color32,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        name: "Dark",
+        dark_mode: true,
+        board_background: Color32::BLACK,
+        board_border: Color32::from_rgba_premultiplied(200, 50, 125, 44),
+        tile_path: Color32::from_rgba_premultiplied(255, 255, 255, 191),
+        tile_hover: Color32::from_rgba_premultiplied(255, 215, 0, 191),
+        rotate_button_background: Color32::BLACK,
+        rotate_button_text: Color32::from_rgba_premultiplied(255, 255, 255, 191),
+        log_error: Color32::LIGHT_RED,
+        log_warn: Color32::YELLOW,
+        log_info: Color32::LIGHT_GRAY,
+        log_debug: Color32::GRAY,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        name: "Light",
+        dark_mode: false,
+        board_background: Color32::from_rgb(238, 232, 213),
+        board_border: Color32::from_rgb(200, 50, 125),
+        tile_path: Color32::from_rgb(60, 60, 60),
+        tile_hover: Color32::from_rgb(190, 140, 0),
+        rotate_button_background: Color32::WHITE,
+        rotate_button_text: Color32::from_rgb(60, 60, 60),
+        log_error: Color32::from_rgb(180, 30, 30),
+        log_warn: Color32::from_rgb(150, 95, 0),
+        log_info: Color32::from_rgb(60, 60, 60),
+        log_debug: Color32::from_rgb(120, 120, 120),
+    };
+
+    pub fn visuals(&self) -> Visuals {
+        if self.dark_mode { Visuals::dark() } else { Visuals::light() }
+    }
+}
+
+impl ThemeKind {
+    pub const ALL: [ThemeKind; 2] = [ThemeKind::Dark, ThemeKind::Light];
+
+    pub fn theme(self) -> &'static Theme {
+        match self {
+            ThemeKind::Dark => &Theme::DARK,
+            ThemeKind::Light => &Theme::LIGHT,
+        }
+    }
+}
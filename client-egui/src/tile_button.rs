@@ -1,22 +1,34 @@
 use eframe::egui::{vec2, Frame, Rect, Sense, Widget};
 
 use tsurust_common::board::*;
+use tsurust_common::game::Game;
 
 use crate::rendering::{paint_tile, paint_tile_button_hoverlay, tile_to_screen_transform};
+use crate::theme::Theme;
 
 pub struct TileButton<'a> {
     tile: &'a mut Tile,
+    theme: &'a Theme,
+    /// The game and player whose flip gets used, only present when playing the flip variant
+    flip: Option<(&'a mut Game, PlayerID)>,
 }
 impl<'a> TileButton<'a> {
-    pub fn new(tile: &'a mut Tile) -> Self {
-        Self { tile }
+    pub fn new(tile: &'a mut Tile, theme: &'a Theme) -> Self {
+        Self { tile, theme, flip: None }
+    }
+
+    /// Flip variant: clicking the middle of the tile mirrors it, as long as `game` still lets `player` flip.
+    pub fn flip_for(mut self, game: &'a mut Game, player: PlayerID) -> Self {
+        self.flip = Some((game, player));
+        self
     }
 }
 impl<'a> Widget for TileButton<'a> {
-    fn ui(self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
+    fn ui(mut self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
         let (rect, response) =
             ui.allocate_exact_size(vec2(120.0, 120.0), Sense::click().union(Sense::hover()));
 
+        let flippable = self.flip.as_ref().is_some_and(|(game, player)| game.can_flip(*player));
         let to_screen = tile_to_screen_transform(rect);
         if response.clicked() {
             if let Some(pos) = response.interact_pointer_pos() {
@@ -25,6 +37,10 @@ impl<'a> Widget for TileButton<'a> {
                     *self.tile = self.tile.rotated(false)
                 } else if pos.x > 2. {
                     *self.tile = self.tile.rotated(true)
+                } else if let Some((game, player)) = &mut self.flip {
+                    if game.use_flip(*player).is_ok() {
+                        *self.tile = self.tile.mirrored();
+                    }
                 }
             }
         }
@@ -35,13 +51,14 @@ impl<'a> Widget for TileButton<'a> {
                 let rect = response.rect;
 
                 if response.hovered() {
-                    paint_tile_button_hoverlay(rect, painter);
+                    paint_tile_button_hoverlay(rect, painter, self.theme, flippable);
                 }
 
                 paint_tile(
                     self.tile,
                     Rect::from_center_size(rect.center(), vec2(119., 119.)),
                     painter,
+                    self.theme,
                 );
             });
         response
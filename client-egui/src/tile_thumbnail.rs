@@ -0,0 +1,29 @@
+use eframe::egui::{vec2, Rect, Sense, Widget};
+
+use tsurust_common::board::*;
+
+use crate::rendering::paint_tile;
+use crate::theme::Theme;
+
+/// A small, non-interactive drawing of a `Tile`, for lists and tooltips.
+pub struct TileThumbnail<'a> {
+    tile: &'a Tile,
+    theme: &'a Theme,
+    size: f32,
+}
+impl<'a> TileThumbnail<'a> {
+    pub fn new(tile: &'a Tile, theme: &'a Theme) -> Self {
+        Self { tile, theme, size: 28. }
+    }
+}
+impl<'a> Widget for TileThumbnail<'a> {
+    fn ui(self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
+        let (rect, response) = ui.allocate_exact_size(vec2(self.size, self.size), Sense::hover());
+
+        let painter = ui.painter();
+        painter.rect_filled(rect, 0.5, self.theme.board_background);
+        paint_tile(self.tile, Rect::from_center_size(rect.center(), rect.size() - vec2(2., 2.)), painter, self.theme);
+
+        response
+    }
+}
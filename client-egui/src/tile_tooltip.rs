@@ -0,0 +1,49 @@
+use eframe::egui::{pos2, vec2, Align2, FontId, Sense, Ui, Vec2};
+
+use tsurust_common::board::*;
+use tsurust_common::catalog::identify;
+
+use crate::rendering::{endpoint_position, paint_tile, tile_to_screen_transform};
+use crate::theme::Theme;
+
+const DIAGRAM_LENGTH: f32 = 96.;
+/// Room around the diagram for the endpoint numbers
+const LABEL_MARGIN: f32 = 14.;
+
+/// Hover details for a tile: its catalog number, and a bigger drawing with every endpoint numbered
+/// next to the list of endpoints each path connects.
+pub fn tile_tooltip(ui: &mut Ui, tile: &Tile, theme: &Theme) {
+    match identify(tile) {
+        Some((id, 0)) => ui.strong(format!("Tile #{}", id)),
+        Some((id, rotations)) => ui.strong(format!("Tile #{}, rotated {}× clockwise", id, rotations)),
+        None => ui.strong("Unknown tile"),
+    };
+
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(DIAGRAM_LENGTH + 2. * LABEL_MARGIN), Sense::hover());
+    let tile_rect = rect.shrink(LABEL_MARGIN);
+    let painter = ui.painter();
+    painter.rect_filled(tile_rect, 0.5, theme.board_background);
+    paint_tile(tile, tile_rect, painter, theme);
+
+    let to_screen = tile_to_screen_transform(tile_rect);
+    for endpoint in 0..8 {
+        let pos = endpoint_position(endpoint);
+        // endpoints sit on the tile's border, push each label out across the side it's on
+        let from_center = pos - pos2(1.5, 1.5);
+        let outwards = if from_center.x.abs() > from_center.y.abs() {
+            vec2(from_center.x.signum(), 0.)
+        } else {
+            vec2(0., from_center.y.signum())
+        };
+        painter.text(
+            to_screen.transform_pos(pos) + outwards * LABEL_MARGIN / 2.,
+            Align2::CENTER_CENTER,
+            endpoint.to_string(),
+            FontId::monospace(11.),
+            ui.visuals().text_color(),
+        );
+    }
+
+    let pairs: Vec<String> = tile.segments.iter().map(|seg| format!("{}–{}", seg.a(), seg.b())).collect();
+    ui.label(format!("Connects {}", pairs.join(", ")));
+}
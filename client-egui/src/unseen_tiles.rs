@@ -0,0 +1,29 @@
+use eframe::egui;
+
+use tsurust_common::board::*;
+use tsurust_common::catalog::{tile_by_id, unseen_tiles, TILE_COUNT};
+
+use crate::theme::Theme;
+use crate::tile_thumbnail::TileThumbnail;
+use crate::tile_tooltip::tile_tooltip;
+
+/// Window listing the tiles that are neither on the board nor in the given hand,
+/// i.e. the ones that may still be drawn from the deck or be held by opponents.
+pub fn unseen_tiles_window(ctx: &egui::Context, open: &mut bool, history: &[Move], hand: &[Tile], theme: &Theme) {
+    egui::Window::new("Unseen tiles")
+        .open(open)
+        .default_width(260.)
+        .show(ctx, |ui| {
+            let unseen = unseen_tiles(history, hand);
+            ui.label(format!("{} of {} tiles unseen", unseen.len(), TILE_COUNT));
+            ui.separator();
+
+            ui.horizontal_wrapped(|ui| {
+                for id in unseen {
+                    if let Some(tile) = tile_by_id(id) {
+                        ui.add(TileThumbnail::new(&tile, theme)).on_hover_ui(|ui| tile_tooltip(ui, &tile, theme));
+                    }
+                }
+            });
+        });
+}
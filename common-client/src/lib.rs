@@ -0,0 +1,95 @@
+//! Client side of the connection to a tsurust server, meant to be shared by every front end
+//! (the egui client, bots, ...). There are no typed game messages yet, so frames are plain text.
+
+use std::sync::Arc;
+
+use ewebsock::{WsEvent, WsMessage, WsReceiver, WsSender};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connecting,
+    Open,
+    Closed,
+}
+
+/// What happened on the connection since the last `poll`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    Connected,
+    Text(String),
+    /// The connection dropped, with the error if there was one. Call `reconnect` to try again.
+    Disconnected(Option<String>),
+}
+
+/// A websocket connection to the server that can be re-established after it drops.
+pub struct GameConnection {
+    url: String,
+    wakeup: Arc<dyn Fn() + Send + Sync>,
+    sender: WsSender,
+    receiver: WsReceiver,
+    state: ConnectionState,
+}
+
+impl GameConnection {
+    /// Starts connecting to `url`. `wakeup` is called from the socket thread whenever an event arrives,
+    /// e.g. to request a repaint of the UI. On native this has to be called from within a Tokio runtime.
+    pub fn connect(url: impl Into<String>, wakeup: impl Fn() + Send + Sync + 'static) -> Result<Self, String> {
+        let url = url.into();
+        let wakeup: Arc<dyn Fn() + Send + Sync> = Arc::new(wakeup);
+        let (sender, receiver) = open(&url, &wakeup)?;
+
+        Ok(GameConnection { url, wakeup, sender, receiver, state: ConnectionState::Connecting })
+    }
+
+    /// Drops the current socket, if any, and connects again to the same server.
+    pub fn reconnect(&mut self) -> Result<(), String> {
+        let (sender, receiver) = open(&self.url, &self.wakeup)?;
+        self.sender = sender;
+        self.receiver = receiver;
+        self.state = ConnectionState::Connecting;
+        Ok(())
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    pub fn send_text(&mut self, text: impl Into<String>) {
+        self.sender.send(WsMessage::Text(text.into()));
+    }
+
+    /// Drains everything received since the last call, without blocking.
+    pub fn poll(&mut self) -> Vec<ConnectionEvent> {
+        let mut events = Vec::new();
+
+        while let Some(event) = self.receiver.try_recv() {
+            match event {
+                WsEvent::Opened => {
+                    self.state = ConnectionState::Open;
+                    events.push(ConnectionEvent::Connected);
+                }
+                WsEvent::Message(WsMessage::Text(text)) => events.push(ConnectionEvent::Text(text)),
+                // the socket answers pings by itself, and the server doesn't send anything else
+                WsEvent::Message(_) => {}
+                WsEvent::Error(error) => {
+                    self.state = ConnectionState::Closed;
+                    events.push(ConnectionEvent::Disconnected(Some(error)));
+                }
+                WsEvent::Closed => {
+                    self.state = ConnectionState::Closed;
+                    events.push(ConnectionEvent::Disconnected(None));
+                }
+            }
+        }
+        events
+    }
+}
+
+fn open(url: &str, wakeup: &Arc<dyn Fn() + Send + Sync>) -> Result<(WsSender, WsReceiver), String> {
+    let wakeup = Arc::clone(wakeup);
+    ewebsock::connect_with_wakeup(url, move || wakeup())
+}
@@ -1,27 +1,26 @@
-use ewebsock::WsMessage;
+use std::time::Duration;
 
+use tsurust_client_core::{ConnectionEvent, GameConnection};
+
+/// Connects to a local server, sends one message and prints whatever comes back.
 #[tokio::main]
 async fn main() {
-    let wakeup = move || {            println!(" loki");
-    }; // wake up UI thread on new message
-    match ewebsock::connect_with_wakeup("ws://127.0.0.1:3030/tsurust-ws/", wakeup )  {
-        Ok((mut ws_sender, ws_receiver)) => {
-            println!(" oki");
-            ws_sender.send(WsMessage::Text("waa".to_string()));
-            println!(" doki");
-
-            while let Some(event) = ws_receiver.try_recv() {
-                dbg!(" {}", event);
-            }
-
-            loop {
+    let mut connection = match GameConnection::connect("ws://127.0.0.1:3030/tsurust-ws/", || {}) {
+        Ok(connection) => connection,
+        Err(error) => {
+            eprintln!("can't connect: {}", error);
+            return;
+        }
+    };
+    connection.send_text("waa");
 
+    loop {
+        for event in connection.poll() {
+            println!("{:?}", event);
+            if let ConnectionEvent::Disconnected(_) = event {
+                return;
             }
         }
-        Err(error) => {
-            dbg!(" noki {}", error);
-        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
     }
-
-
 }
@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use tsurust_common::board::*;
+use tsurust_common::catalog::all_tiles;
+use tsurust_common::game::Game;
+
+/// Every catalog tile placed row by row, leaving only the last cell empty, so paths are as long as they get.
+fn dense_history() -> Vec<Move> {
+    all_tiles()
+        .iter()
+        .enumerate()
+        .map(|(i, &tile)| {
+            let tile = (0..i % 4).fold(tile, |tile, _| tile.rotated(true));
+            Move { tile, cell: CellCoord { row: i / BOARD_LENGTH, col: i % BOARD_LENGTH }, player_id: i % 4 }
+        })
+        .collect()
+}
+
+fn players() -> Vec<Player> {
+    (0..4)
+        .map(|id| Player { id, pos: PlayerPos::new(MIN, id, 5), alive: true })
+        .collect()
+}
+
+fn traverse_dense_board(c: &mut Criterion) {
+    let mut board = Board::new();
+    dense_history().into_iter().for_each(|mov| board.place_tile(mov));
+    let starts = PlayerPos::valid_spawn_positions();
+
+    c.bench_function("traverse_from on a dense board", |b| {
+        b.iter(|| {
+            for &start in &starts {
+                black_box(board.traverse_from(black_box(start)));
+            }
+        })
+    });
+}
+
+fn perform_move(c: &mut Criterion) {
+    let mov = dense_history()[0];
+
+    c.bench_function("Game::perform_move", |b| {
+        b.iter_batched(
+            || Game::new(players()),
+            |mut game| game.perform_move(black_box(mov)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn replay_full_game(c: &mut Criterion) {
+    let history = dense_history();
+
+    c.bench_function("replay a full game", |b| {
+        b.iter(|| {
+            let mut board = Board::new();
+            let mut positions: Vec<PlayerPos> = players().iter().map(|player| player.pos).collect();
+            for &mov in &history {
+                board.place_tile(mov);
+                for pos in positions.iter_mut() {
+                    *pos = board.traverse_from(*pos);
+                }
+            }
+            black_box(positions)
+        })
+    });
+}
+
+criterion_group!(benches, traverse_dense_board, perform_move, replay_full_game);
+criterion_main!(benches);
@@ -55,6 +55,7 @@ pub struct Move {
 }
 /// Board state history: all the moves that have been played
 /// Defines functions to calculate the effects of moves on the board's state
+#[derive(Default)]
 pub struct Board {
     //setup: Vec<Player> something reflecting initial conditions?
     pub history: Vec<Move>,
@@ -76,6 +77,16 @@ impl Tile {
             .collect();
         Tile::new(rotated_segments.into_inner().unwrap())
     }
+
+    /// Returns a new `Tile` that's the mirror image of this one, flipped across its vertical axis.
+    pub fn mirrored(&self) -> Tile {
+        let mirrored_segments: ArrayVec<Segment, 4> = self
+            .segments
+            .into_iter()
+            .map(|seg| seg.mirrored())
+            .collect();
+        Tile::new(mirrored_segments.into_inner().unwrap())
+    }
 }
 
 impl Segment {
@@ -92,6 +103,15 @@ impl Segment {
         self.b
     }
 
+    /// Swaps left and right: 0 <-> 1 on the bottom, 4 <-> 5 on top and the left side's endpoints with the right side's.
+    pub fn mirrored(&self) -> Segment {
+        let num_endpoints = 8;
+        Segment::new(
+            (num_endpoints + 1 - self.a) % num_endpoints,
+            (num_endpoints + 1 - self.b) % num_endpoints,
+        )
+    }
+
     pub fn rotated(&self, clockwise: bool) -> Segment {
         let num_endpoints = 8;
         let offset = if clockwise { 6 } else { 2 };
@@ -120,6 +140,21 @@ impl PlayerPos {
             _ => false,
         }
     }
+
+    /// Can a player start here? Pawns start on the outer edge, facing into the board.
+    pub fn is_valid_spawn(&self) -> bool {
+        self.on_edge()
+    }
+
+    /// Every position a player can start from, going clockwise around the board from the top left corner.
+    pub fn valid_spawn_positions() -> Vec<PlayerPos> {
+        let top = (MIN..=MAX).flat_map(|col| [PlayerPos::new(MIN, col, 5), PlayerPos::new(MIN, col, 4)]);
+        let right = (MIN..=MAX).flat_map(|row| [PlayerPos::new(row, MAX, 3), PlayerPos::new(row, MAX, 2)]);
+        let bottom = (MIN..=MAX).rev().flat_map(|col| [PlayerPos::new(MAX, col, 1), PlayerPos::new(MAX, col, 0)]);
+        let left = (MIN..=MAX).rev().flat_map(|row| [PlayerPos::new(row, MIN, 7), PlayerPos::new(row, MIN, 6)]);
+
+        top.chain(right).chain(bottom).chain(left).collect()
+    }
 }
 
 impl Board {
@@ -144,13 +179,22 @@ impl Board {
 
     /// Returns the final position after traversing the path starting at the given position
     pub fn traverse_from(&self, starting_point: PlayerPos) -> PlayerPos {
-        match self.get_tile_at(starting_point.cell) {
-            None => starting_point, // There is no tile to follow its path, so we're done
-            Some(tile) => {
-                let next_pos = Board::traverse_tile(tile, starting_point);
-                self.traverse_from(next_pos)
+        self.traverse_counting_tiles(starting_point).0
+    }
+
+    /// Like `traverse_from`, but also returns how many tiles the path went through.
+    pub fn traverse_counting_tiles(&self, starting_point: PlayerPos) -> (PlayerPos, usize) {
+        let mut pos = starting_point;
+        let mut tiles = 0;
+        // When there is no tile to follow its path, we're done
+        while let Some(tile) = self.get_tile_at(pos.cell) {
+            pos = Board::traverse_tile(tile, pos);
+            tiles += 1;
+            if pos.on_edge() {
+                break; // The path led off the board; following the tile again would walk back along it
             }
         }
+        (pos, tiles)
     }
 
     /// Returns the immediate next position of a player starting at the given position and following the path of the given `Tile`
@@ -194,9 +238,40 @@ pub fn seg(a: TileEndpoint, b: TileEndpoint) -> Segment {
     Segment::new(a, b)
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn mirroring_swaps_left_and_right() {
+        let tile = Tile::new([seg(0, 2), seg(1, 3), seg(4, 6), seg(5, 7)]);
+
+        assert_eq!(tile.mirrored(), Tile::new([seg(1, 7), seg(0, 6), seg(5, 3), seg(4, 2)]));
+        assert_eq!(tile.mirrored().mirrored(), tile);
+    }
+
+    #[test]
+    fn spawn_positions_go_around_the_whole_edge() {
+        let spawns = PlayerPos::valid_spawn_positions();
+
+        assert_eq!(spawns.len(), 4 * 2 * BOARD_LENGTH);
+        assert!(spawns.iter().all(PlayerPos::is_valid_spawn));
+        assert_eq!(spawns[0], PlayerPos::new(MIN, MIN, 5));
+        assert_eq!(spawns[spawns.len() - 1], PlayerPos::new(MIN, MIN, 6));
+
+        let mut distinct = spawns.clone();
+        distinct.sort_by_key(|pos| (pos.cell.row, pos.cell.col, pos.endpoint));
+        distinct.dedup();
+        assert_eq!(distinct.len(), spawns.len());
+    }
+
+    #[test]
+    fn interior_positions_are_not_spawns() {
+        assert!(!PlayerPos::new(MIN, MIN, 0).is_valid_spawn());
+        assert!(!PlayerPos::new(2, 3, 4).is_valid_spawn());
+        assert!(!PlayerPos::new(MAX, MAX, 7).is_valid_spawn());
+    }
+
     #[test]
     fn test_next_pos_edge() {
         let tile = Tile::new([seg(5, 3), seg(6, 7), seg(4, 0), seg(1, 2)]);
@@ -222,6 +297,28 @@ mod tests {
         assert_eq!(Board::traverse_tile(&tile, from), PlayerPos::new(0, 1, 7));
     }
 
+    #[test]
+    fn traverse_from_stops_at_the_edge() {
+        let mut board = Board::new();
+        // straight paths: 5 <-> 0 and 4 <-> 1 connect top and bottom
+        let tile = Tile::new([seg(0, 5), seg(1, 4), seg(2, 7), seg(3, 6)]);
+        for row in MIN..=MAX {
+            board.place_tile(Move { tile, cell: CellCoord { row, col: 0 }, player_id: 1 });
+        }
+
+        assert_eq!(board.traverse_from(PlayerPos::new(MIN, 0, 5)), PlayerPos::new(MAX, 0, 0));
+        assert_eq!(board.traverse_from(PlayerPos::new(MAX, 0, 1)), PlayerPos::new(MIN, 0, 4));
+    }
+
+    #[test]
+    fn traverse_from_stops_at_an_empty_cell() {
+        let mut board = Board::new();
+        let tile = Tile::new([seg(0, 5), seg(1, 4), seg(2, 7), seg(3, 6)]);
+        board.place_tile(Move { tile, cell: CellCoord { row: 0, col: 0 }, player_id: 1 });
+
+        assert_eq!(board.traverse_from(PlayerPos::new(0, 0, 5)), PlayerPos::new(1, 0, 5));
+    }
+
     #[test]
     fn test_neighboring_entry() {
         assert_eq!(Board::neighboring_entry(0), 5);
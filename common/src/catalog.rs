@@ -0,0 +1,146 @@
+use std::sync::OnceLock;
+
+use crate::board::*;
+
+/// There is exactly one of each distinct tile in the game.
+pub const TILE_COUNT: usize = 35;
+
+/// A tile's canonical number, from 1 to `TILE_COUNT`. Rotating a tile doesn't change its id.
+pub type TileId = usize;
+
+/// Every distinct tile, in canonical order: the tile with id `n` is at index `n - 1`.
+pub fn all_tiles() -> &'static [Tile; TILE_COUNT] {
+    static CATALOG: OnceLock<[Tile; TILE_COUNT]> = OnceLock::new();
+    CATALOG.get_or_init(|| [
+        Tile::new([seg(0, 1), seg(2, 3), seg(4, 5), seg(6, 7)]),
+        Tile::new([seg(0, 1), seg(2, 3), seg(4, 6), seg(5, 7)]),
+        Tile::new([seg(0, 1), seg(2, 3), seg(4, 7), seg(5, 6)]),
+        Tile::new([seg(0, 1), seg(2, 4), seg(3, 6), seg(5, 7)]),
+        Tile::new([seg(0, 1), seg(2, 4), seg(3, 7), seg(5, 6)]),
+        Tile::new([seg(0, 1), seg(2, 5), seg(3, 6), seg(4, 7)]),
+        Tile::new([seg(0, 1), seg(2, 5), seg(3, 7), seg(4, 6)]),
+        Tile::new([seg(0, 1), seg(2, 6), seg(3, 4), seg(5, 7)]),
+        Tile::new([seg(0, 1), seg(2, 6), seg(3, 5), seg(4, 7)]),
+        Tile::new([seg(0, 1), seg(2, 6), seg(3, 7), seg(4, 5)]),
+        Tile::new([seg(0, 1), seg(2, 7), seg(3, 4), seg(5, 6)]),
+        Tile::new([seg(0, 1), seg(2, 7), seg(3, 5), seg(4, 6)]),
+        Tile::new([seg(0, 1), seg(2, 7), seg(3, 6), seg(4, 5)]),
+        Tile::new([seg(0, 2), seg(1, 3), seg(4, 6), seg(5, 7)]),
+        Tile::new([seg(0, 2), seg(1, 3), seg(4, 7), seg(5, 6)]),
+        Tile::new([seg(0, 2), seg(1, 4), seg(3, 6), seg(5, 7)]),
+        Tile::new([seg(0, 2), seg(1, 4), seg(3, 7), seg(5, 6)]),
+        Tile::new([seg(0, 2), seg(1, 5), seg(3, 6), seg(4, 7)]),
+        Tile::new([seg(0, 2), seg(1, 5), seg(3, 7), seg(4, 6)]),
+        Tile::new([seg(0, 2), seg(1, 6), seg(3, 4), seg(5, 7)]),
+        Tile::new([seg(0, 2), seg(1, 6), seg(3, 5), seg(4, 7)]),
+        Tile::new([seg(0, 2), seg(1, 7), seg(3, 4), seg(5, 6)]),
+        Tile::new([seg(0, 2), seg(1, 7), seg(3, 5), seg(4, 6)]),
+        Tile::new([seg(0, 3), seg(1, 2), seg(4, 7), seg(5, 6)]),
+        Tile::new([seg(0, 3), seg(1, 4), seg(2, 6), seg(5, 7)]),
+        Tile::new([seg(0, 3), seg(1, 4), seg(2, 7), seg(5, 6)]),
+        Tile::new([seg(0, 3), seg(1, 5), seg(2, 6), seg(4, 7)]),
+        Tile::new([seg(0, 3), seg(1, 6), seg(2, 5), seg(4, 7)]),
+        Tile::new([seg(0, 4), seg(1, 2), seg(3, 6), seg(5, 7)]),
+        Tile::new([seg(0, 4), seg(1, 2), seg(3, 7), seg(5, 6)]),
+        Tile::new([seg(0, 4), seg(1, 3), seg(2, 6), seg(5, 7)]),
+        Tile::new([seg(0, 4), seg(1, 5), seg(2, 6), seg(3, 7)]),
+        Tile::new([seg(0, 4), seg(1, 5), seg(2, 7), seg(3, 6)]),
+        Tile::new([seg(0, 5), seg(1, 4), seg(2, 7), seg(3, 6)]),
+        Tile::new([seg(0, 7), seg(1, 2), seg(3, 4), seg(5, 6)]),
+    ])
+}
+
+/// The tile with the given id, in its canonical orientation.
+pub fn tile_by_id(id: TileId) -> Option<Tile> {
+    id.checked_sub(1).and_then(|i| all_tiles().get(i)).copied()
+}
+
+/// Finds which catalog tile this is and how many clockwise rotations take the canonical orientation to this one.
+/// For symmetrical tiles the smallest such rotation count is returned.
+pub fn identify(tile: &Tile) -> Option<(TileId, usize)> {
+    all_tiles().iter().enumerate().find_map(|(i, canonical)| {
+        let mut rotated = *canonical;
+        for rotations in 0..4 {
+            if rotated == *tile {
+                return Some((i + 1, rotations));
+            }
+            rotated = rotated.rotated(true);
+        }
+        None
+    })
+}
+
+/// The canonical id of a tile in any orientation.
+pub fn tile_id(tile: &Tile) -> Option<TileId> {
+    identify(tile).map(|(id, _)| id)
+}
+
+/// Ids of the tiles a player hasn't seen yet: everything that's neither on the board nor in their own hand.
+/// These are the tiles that may still be in the deck or in opponents' hands.
+pub fn unseen_tiles(history: &[Move], hand: &[Tile]) -> Vec<TileId> {
+    let mut seen = [false; TILE_COUNT];
+    let placed = history.iter().map(|mov| &mov.tile);
+    for id in placed.chain(hand).filter_map(tile_id) {
+        seen[id - 1] = true;
+    }
+
+    (1..=TILE_COUNT).filter(|id| !seen[id - 1]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::*;
+    use crate::catalog::*;
+
+    #[test]
+    fn catalog_tiles_are_distinct_under_rotation() {
+        for (i, tile) in all_tiles().iter().enumerate() {
+            assert_eq!(identify(tile), Some((i + 1, 0)));
+        }
+    }
+
+    #[test]
+    fn rotations_keep_the_same_id() {
+        let tile = tile_by_id(17).unwrap();
+
+        assert_eq!(identify(&tile.rotated(true)), Some((17, 1)));
+        assert_eq!(identify(&tile.rotated(true).rotated(true)), Some((17, 2)));
+        assert_eq!(identify(&tile.rotated(false)), Some((17, 3)));
+    }
+
+    #[test]
+    fn symmetrical_tiles_report_no_rotation() {
+        let tile = Tile::new([seg(0, 5), seg(1, 4), seg(6, 3), seg(7, 2)]);
+
+        assert_eq!(identify(&tile.rotated(true)), Some((34, 0)));
+    }
+
+    #[test]
+    fn mirrored_tiles_are_catalog_tiles() {
+        for tile in all_tiles() {
+            assert!(identify(&tile.mirrored()).is_some(), "{:?}", tile);
+            assert_eq!(tile.mirrored().mirrored(), *tile);
+        }
+    }
+
+    #[test]
+    fn ids_are_one_based() {
+        assert_eq!(tile_by_id(0), None);
+        assert_eq!(tile_by_id(1), Some(all_tiles()[0]));
+        assert_eq!(tile_by_id(TILE_COUNT), Some(all_tiles()[TILE_COUNT - 1]));
+        assert_eq!(tile_by_id(TILE_COUNT + 1), None);
+    }
+
+    #[test]
+    fn unseen_tiles_excludes_board_and_hand() {
+        let placed = Move { tile: tile_by_id(3).unwrap().rotated(true), cell: CellCoord { row: 0, col: 0 }, player_id: 1 };
+        let hand = [tile_by_id(1).unwrap(), tile_by_id(35).unwrap()];
+
+        let unseen = unseen_tiles(&[placed], &hand);
+        assert_eq!(unseen.len(), TILE_COUNT - 3);
+        assert!(!unseen.contains(&1));
+        assert!(!unseen.contains(&3));
+        assert!(!unseen.contains(&35));
+        assert!(unseen.contains(&2));
+    }
+}
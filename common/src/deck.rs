@@ -2,6 +2,7 @@ use rand::seq::SliceRandom;
 use rand::thread_rng;
 
 use crate::board::*;
+use crate::catalog::all_tiles;
 
 #[derive(Debug)]
 pub struct Deck {
@@ -13,53 +14,26 @@ impl Deck {
         self.tiles.pop()
     }
 
+    /// How many tiles are left to draw
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
     pub fn take_up_to(&mut self, n: usize) -> Vec<Tile> {
         let new_len = self.tiles.len().saturating_sub(n);
         self.tiles.split_off(new_len)
     }
 
-    pub fn put(&mut self, tiles: &Vec<Tile>) {
+    pub fn put(&mut self, _tiles: &[Tile]) {
         // self.tiles.append(&tiles)
     }
 
     pub fn new() -> Deck {
-        let mut tiles = vec![
-            Tile::new([seg(0, 1), seg(2, 3), seg(4, 5), seg(6, 7)]),
-            Tile::new([seg(0, 1), seg(2, 3), seg(4, 6), seg(5, 7)]),
-            Tile::new([seg(0, 1), seg(2, 3), seg(4, 7), seg(5, 6)]),
-            Tile::new([seg(0, 1), seg(2, 4), seg(3, 6), seg(5, 7)]),
-            Tile::new([seg(0, 1), seg(2, 4), seg(3, 7), seg(5, 6)]),
-            Tile::new([seg(0, 1), seg(2, 5), seg(3, 6), seg(4, 7)]),
-            Tile::new([seg(0, 1), seg(2, 5), seg(3, 7), seg(4, 6)]),
-            Tile::new([seg(0, 1), seg(2, 6), seg(3, 4), seg(5, 7)]),
-            Tile::new([seg(0, 1), seg(2, 6), seg(3, 5), seg(4, 7)]),
-            Tile::new([seg(0, 1), seg(2, 6), seg(3, 7), seg(4, 5)]),
-            Tile::new([seg(0, 1), seg(2, 7), seg(3, 4), seg(5, 6)]),
-            Tile::new([seg(0, 1), seg(2, 7), seg(3, 5), seg(4, 6)]),
-            Tile::new([seg(0, 1), seg(2, 7), seg(3, 6), seg(4, 5)]),
-            Tile::new([seg(0, 2), seg(1, 3), seg(4, 6), seg(5, 7)]),
-            Tile::new([seg(0, 2), seg(1, 3), seg(4, 7), seg(5, 6)]),
-            Tile::new([seg(0, 2), seg(1, 4), seg(3, 6), seg(5, 7)]),
-            Tile::new([seg(0, 2), seg(1, 4), seg(3, 7), seg(5, 6)]),
-            Tile::new([seg(0, 2), seg(1, 5), seg(3, 6), seg(4, 7)]),
-            Tile::new([seg(0, 2), seg(1, 5), seg(3, 7), seg(4, 6)]),
-            Tile::new([seg(0, 2), seg(1, 6), seg(3, 4), seg(5, 7)]),
-            Tile::new([seg(0, 2), seg(1, 6), seg(3, 5), seg(4, 7)]),
-            Tile::new([seg(0, 2), seg(1, 7), seg(3, 4), seg(5, 6)]),
-            Tile::new([seg(0, 2), seg(1, 7), seg(3, 5), seg(4, 6)]),
-            Tile::new([seg(0, 3), seg(1, 2), seg(4, 7), seg(5, 6)]),
-            Tile::new([seg(0, 3), seg(1, 4), seg(2, 6), seg(5, 7)]),
-            Tile::new([seg(0, 3), seg(1, 4), seg(2, 7), seg(5, 6)]),
-            Tile::new([seg(0, 3), seg(1, 5), seg(2, 6), seg(4, 7)]),
-            Tile::new([seg(0, 3), seg(1, 6), seg(2, 5), seg(4, 7)]),
-            Tile::new([seg(0, 4), seg(1, 2), seg(3, 6), seg(5, 7)]),
-            Tile::new([seg(0, 4), seg(1, 2), seg(3, 7), seg(5, 6)]),
-            Tile::new([seg(0, 4), seg(1, 3), seg(2, 6), seg(5, 7)]),
-            Tile::new([seg(0, 4), seg(1, 5), seg(2, 6), seg(3, 7)]),
-            Tile::new([seg(0, 4), seg(1, 5), seg(2, 7), seg(3, 6)]),
-            Tile::new([seg(0, 5), seg(1, 4), seg(2, 7), seg(3, 6)]),
-            Tile::new([seg(0, 7), seg(1, 2), seg(3, 4), seg(5, 6)]),
-        ];
+        let mut tiles = all_tiles().to_vec();
 
         let mut rng = thread_rng();
         tiles.shuffle(&mut rng);
@@ -68,6 +42,7 @@ impl Deck {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::deck::Deck;
 
@@ -80,4 +55,17 @@ mod tests {
         assert_eq!(deck.take_up_to(50).len(), 31); // deck is only 35 tiles, so the remaining 31 should pop off
         assert!(deck.take().is_none()); //depleted
     }
+
+    #[test]
+    fn len_tracks_remaining_tiles() {
+        let mut deck = Deck::new();
+
+        assert_eq!(deck.len(), 35);
+        assert!(!deck.is_empty());
+        deck.take();
+        assert_eq!(deck.len(), 34);
+        deck.take_up_to(50);
+        assert_eq!(deck.len(), 0);
+        assert!(deck.is_empty());
+    }
 }
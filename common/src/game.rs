@@ -1,31 +1,88 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
 
 use crate::board::*;
 use crate::deck::Deck;
 
+/// Rules that can be tuned for each game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameConfig {
+    hand_size: usize,
+}
+
+impl GameConfig {
+    pub const HAND_SIZES: RangeInclusive<usize> = 2..=5;
+
+    pub fn with_hand_size(hand_size: usize) -> Result<GameConfig, &'static str> {
+        if Self::HAND_SIZES.contains(&hand_size) {
+            Ok(GameConfig { hand_size })
+        } else {
+            Err("hand size must be between 2 and 5")
+        }
+    }
+
+    /// How many tiles each player is dealt at the start, and has their hand refilled to after every turn.
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig { hand_size: 3 }
+    }
+}
+
 pub struct Game {
     pub deck: Deck,
     pub board: Board,
     pub players: Vec<Player>,
     hands: HashMap<PlayerID, Vec<Tile>>,
+    config: GameConfig,
+    /// Players who have used their one flip of the game, for the flip variant
+    flips_used: HashSet<PlayerID>,
+    #[allow(dead_code)]
     dragon: Option<PlayerID>,
 }
 
 impl Game {
     pub fn new(players: Vec<Player>) -> Game {
+        Game::with_config(players, GameConfig::default())
+    }
+
+    pub fn with_config(players: Vec<Player>, config: GameConfig) -> Game {
         let mut deck = Deck::new();
         let mut hands = HashMap::new();
-        for mut player in &players {
-            hands.insert(player.id, deck.take_up_to(3));
+        for player in &players {
+            hands.insert(player.id, deck.take_up_to(config.hand_size()));
         }
 
         Game {
-            players, hands, deck,
+            players, hands, deck, config,
             board: Board::new(),
+            flips_used: HashSet::new(),
             dragon: None,
         }
     }
 
+    pub fn config(&self) -> GameConfig {
+        self.config
+    }
+
+    /// Flip variant: each player may mirror a tile instead of rotating it once per game.
+    pub fn can_flip(&self, player: PlayerID) -> bool {
+        !self.flips_used.contains(&player)
+    }
+
+    /// Uses up `player`'s flip, fails if they already flipped a tile this game.
+    pub fn use_flip(&mut self, player: PlayerID) -> Result<(), &'static str> {
+        if self.flips_used.insert(player) {
+            Ok(())
+        } else {
+            Err("flip already used this game")
+        }
+    }
+
     pub fn perform_move(&mut self, mov: Move) {
         // to-do: check player is current player
         // to-do: introduce TurnResult type or similar, design that api
@@ -34,34 +91,449 @@ impl Game {
 
         self.board.place_tile(mov);
 
-        //self.update_players();
+        // to-do: report the eliminated players once there's a TurnResult
+        self.update_players(mov.cell);
 
         //self.fill_hands();
 
         //self.complete_turn(mov.player_id);
     }
 
+    /// A cheap, deterministic hash of the authoritative state: board history, player positions and
+    /// how many tiles each player holds. Two peers holding the same state compute the same value
+    /// regardless of platform, so comparing hashes is enough to detect a desync.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+
+        for mov in &self.board.history {
+            hasher.write_move(mov);
+        }
+        for player in &self.players {
+            hasher.write(player.id);
+            hasher.write_pos(player.pos);
+            hasher.write(player.alive as usize);
+            hasher.write(self.hands.get(&player.id).map_or(0, Vec::len));
+            hasher.write(self.flips_used.contains(&player.id) as usize);
+        }
+
+        hasher.finish()
+    }
+
+    #[allow(dead_code)]
     fn deduct_tile_from_hand(&mut self, mov: Move) -> Result<(), &'static str> {
         match self.hands[&mov.player_id].iter().find(|&tile| {tile.eq(&mov.tile)}) {
             Some(_) => Ok(()),
             _ => Err("fuck the client"),
         }
     }
-    fn update_players(&mut self) {
-        for mut player in alive_players(&mut self.players) {
-            let new_pos = self.board.traverse_from(player.pos);
-            player.pos = new_pos;
-            player.alive = !new_pos.on_edge();
+    /// Moves every pawn standing in front of the cell a tile was just placed on, and returns the players
+    /// that were eliminated by it.
+    ///
+    /// Eliminations are listed in a fixed order so every peer agrees on who went out first: pawns whose path
+    /// off the board crossed fewer tiles go out first, and ties are broken by seating order in `players`.
+    fn update_players(&mut self, placed: CellCoord) -> Vec<PlayerID> {
+        // (tiles crossed, seat, player) sorts into the resolution order
+        let mut eliminated = Vec::new();
+        for (seat, player) in self.players.iter_mut().enumerate() {
+            if !player.alive || player.pos.cell != placed {
+                continue;
+            }
 
-            if !player.alive {
-                //self.deck.
+            let (new_pos, tiles) = self.board.traverse_counting_tiles(player.pos);
+            player.pos = new_pos;
+            if new_pos.on_edge() {
+                player.alive = false;
+                eliminated.push((tiles, seat, player.id));
             }
         }
+
+        eliminated.sort();
+        eliminated.into_iter().map(|(_, _, id)| id).collect()
+    }
+    #[allow(dead_code)]
+    fn fill_hands(&mut self) {
+        for player in self.players.iter().filter(|player| player.alive) {
+            let hand = self.hands.entry(player.id).or_default();
+            let missing = self.config.hand_size().saturating_sub(hand.len());
+            hand.extend(self.deck.take_up_to(missing));
+        }
+    }
+    #[allow(dead_code)]
+    fn complete_turn(&self, _for_player: PlayerID) {}
+}
+
+/// FNV-1a over fixed-width words. `std`'s `DefaultHasher` is not guaranteed to be stable across
+/// Rust releases and `usize` differs between wasm32 and native, so neither can be used for hashes
+/// that get compared between the server and clients.
+struct StateHasher(u64);
+
+impl StateHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> StateHasher {
+        StateHasher(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, value: usize) {
+        for byte in (value as u64).to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_pos(&mut self, pos: PlayerPos) {
+        self.write(pos.cell.row);
+        self.write(pos.cell.col);
+        self.write(pos.endpoint);
+    }
+
+    fn write_move(&mut self, mov: &Move) {
+        self.write(mov.player_id);
+        self.write(mov.cell.row);
+        self.write(mov.cell.col);
+        for segment in mov.tile.segments {
+            self.write(segment.a);
+            self.write(segment.b);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
     }
-    fn fill_hands(&self) {}
-    fn complete_turn(&self, for_player: PlayerID) {}
 }
 
-fn alive_players(players: &mut Vec<Player>) -> Vec<&mut Player> {
-    players.iter_mut().filter(|player| player.alive).collect()
+#[cfg(test)]
+mod tests {
+    use crate::board::*;
+    use crate::catalog::TILE_COUNT;
+    use crate::game::{Game, GameConfig};
+
+    fn two_player_game() -> Game {
+        Game::new(vec![
+            Player { id: 1, pos: PlayerPos::new(0, 0, 5), alive: true },
+            Player { id: 2, pos: PlayerPos::new(5, 5, 1), alive: true },
+        ])
+    }
+
+    fn some_move(row: usize, col: usize) -> Move {
+        let tile = Tile::new([seg(0, 1), seg(2, 3), seg(4, 5), seg(6, 7)]);
+        Move { tile, cell: CellCoord { row, col }, player_id: 1 }
+    }
+
+    fn place(game: &mut Game, row: usize, col: usize, tile: Tile) {
+        game.board.place_tile(Move { tile, cell: CellCoord { row, col }, player_id: 1 });
+    }
+
+    #[test]
+    fn pawns_going_out_on_the_same_tile_are_eliminated_in_seating_order() {
+        let mut game = Game::new(vec![
+            Player { id: 7, pos: PlayerPos::new(0, 0, 6), alive: true },
+            Player { id: 3, pos: PlayerPos::new(0, 0, 5), alive: true },
+        ]);
+        place(&mut game, 0, 0, Tile::new([seg(0, 1), seg(2, 3), seg(4, 5), seg(6, 7)]));
+
+        assert_eq!(game.update_players(CellCoord { row: 0, col: 0 }), vec![7, 3]);
+        assert_eq!(game.players[0].pos, PlayerPos::new(0, 0, 7));
+        assert_eq!(game.players[1].pos, PlayerPos::new(0, 0, 4));
+    }
+
+    #[test]
+    fn shorter_paths_off_the_board_are_eliminated_first() {
+        let mut game = Game::new(vec![
+            Player { id: 1, pos: PlayerPos::new(0, 0, 5), alive: true },
+            Player { id: 2, pos: PlayerPos::new(0, 0, 6), alive: true },
+            Player { id: 3, pos: PlayerPos::new(5, 5, 1), alive: true },
+        ]);
+        // player 1 is led right into (0, 1) and out its top, player 2 straight out the left side
+        place(&mut game, 0, 1, Tile::new([seg(4, 7), seg(0, 1), seg(2, 3), seg(5, 6)]));
+        place(&mut game, 0, 0, Tile::new([seg(2, 5), seg(6, 7), seg(0, 1), seg(3, 4)]));
+
+        assert_eq!(game.update_players(CellCoord { row: 0, col: 0 }), vec![2, 1]);
+        assert_eq!(game.players[0].pos, PlayerPos::new(0, 1, 4));
+        assert!(!game.players[0].alive && !game.players[1].alive);
+        // nobody was in front of the new tile, so player 3 doesn't move
+        assert!(game.players[2].alive);
+        assert_eq!(game.players[2].pos, PlayerPos::new(5, 5, 1));
+    }
+
+    #[test]
+    fn pawns_stopping_in_front_of_an_empty_cell_survive() {
+        let mut game = Game::new(vec![Player { id: 1, pos: PlayerPos::new(0, 0, 5), alive: true }]);
+        place(&mut game, 0, 0, Tile::new([seg(2, 5), seg(6, 7), seg(0, 1), seg(3, 4)]));
+
+        assert!(game.update_players(CellCoord { row: 0, col: 0 }).is_empty());
+        assert!(game.players[0].alive);
+        assert_eq!(game.players[0].pos, PlayerPos::new(0, 1, 7));
+    }
+
+    #[test]
+    fn performing_a_move_moves_the_pawns_in_front_of_it() {
+        let mut game = two_player_game();
+        game.perform_move(Move {
+            tile: Tile::new([seg(2, 5), seg(6, 7), seg(0, 1), seg(3, 4)]),
+            cell: CellCoord { row: 0, col: 0 },
+            player_id: 1,
+        });
+
+        assert!(game.players[0].alive);
+        assert_eq!(game.players[0].pos, PlayerPos::new(0, 1, 7));
+        assert_eq!(game.players[1].pos, PlayerPos::new(5, 5, 1));
+    }
+
+    #[test]
+    fn hands_are_dealt_to_the_configured_size() {
+        for hand_size in GameConfig::HAND_SIZES {
+            let config = GameConfig::with_hand_size(hand_size).unwrap();
+            let game = Game::with_config(vec![Player { id: 1, pos: PlayerPos::new(0, 0, 5), alive: true }], config);
+
+            assert_eq!(game.hands[&1].len(), hand_size);
+            assert_eq!(game.deck.len(), TILE_COUNT - hand_size);
+        }
+        assert_eq!(two_player_game().config().hand_size(), 3);
+    }
+
+    #[test]
+    fn hand_size_must_be_between_2_and_5() {
+        assert!(GameConfig::with_hand_size(1).is_err());
+        assert!(GameConfig::with_hand_size(6).is_err());
+    }
+
+    #[test]
+    fn hands_are_refilled_up_to_the_hand_size() {
+        let mut game = Game::with_config(
+            vec![Player { id: 1, pos: PlayerPos::new(0, 0, 5), alive: true }],
+            GameConfig::with_hand_size(4).unwrap(),
+        );
+        game.hands.get_mut(&1).unwrap().truncate(1);
+
+        game.fill_hands();
+        assert_eq!(game.hands[&1].len(), 4);
+        assert_eq!(game.deck.len(), TILE_COUNT - 7);
+    }
+
+    #[test]
+    fn state_hash_ignores_hand_contents() {
+        // decks are shuffled, so hands differ between these games but their sizes don't
+        assert_eq!(two_player_game().state_hash(), two_player_game().state_hash());
+    }
+
+    #[test]
+    fn each_player_can_flip_once() {
+        let mut game = two_player_game();
+
+        assert!(game.use_flip(1).is_ok());
+        assert!(!game.can_flip(1));
+        assert!(game.use_flip(1).is_err());
+        // the other player still has theirs
+        assert!(game.can_flip(2));
+        assert!(game.use_flip(2).is_ok());
+    }
+
+    #[test]
+    fn state_hash_changes_when_a_flip_is_used() {
+        let mut game = two_player_game();
+        let before = game.state_hash();
+
+        game.use_flip(2).unwrap();
+        assert_ne!(game.state_hash(), before);
+    }
+
+    #[test]
+    fn state_hash_changes_with_moves() {
+        let mut game = two_player_game();
+        let before = game.state_hash();
+
+        game.perform_move(some_move(0, 0));
+        assert_ne!(game.state_hash(), before);
+    }
+
+    #[test]
+    fn state_hash_depends_on_move_order() {
+        let mut a = two_player_game();
+        a.perform_move(some_move(0, 0));
+        a.perform_move(some_move(0, 1));
+
+        let mut b = two_player_game();
+        b.perform_move(some_move(0, 1));
+        b.perform_move(some_move(0, 0));
+
+        assert_ne!(a.state_hash(), b.state_hash());
+    }
+
+    #[test]
+    fn state_hash_changes_with_player_positions() {
+        let mut game = two_player_game();
+        let before = game.state_hash();
+
+        game.players[0].pos = PlayerPos::new(0, 0, 4);
+        assert_ne!(game.state_hash(), before);
+    }
+
+    mod invariants {
+        use proptest::prelude::*;
+
+        use crate::board::*;
+        use crate::catalog::{all_tiles, TILE_COUNT};
+        use crate::game::Game;
+        use crate::notation::{decode_game, encode_game};
+
+        /// A playthrough's worth of moves: distinct catalog tiles in random orientations, placed on distinct cells.
+        fn history() -> impl Strategy<Value = Vec<Move>> {
+            let cells: Vec<CellCoord> = (0..BOARD_LENGTH * BOARD_LENGTH)
+                .map(|i| CellCoord { row: i / BOARD_LENGTH, col: i % BOARD_LENGTH })
+                .collect();
+
+            (0..=TILE_COUNT)
+                .prop_flat_map(move |len| {
+                    (
+                        Just(cells.clone()).prop_shuffle(),
+                        Just(all_tiles().to_vec()).prop_shuffle(),
+                        prop::collection::vec((0..4usize, 0..4usize), len),
+                    )
+                })
+                .prop_map(|(cells, tiles, turns)| {
+                    turns
+                        .into_iter()
+                        .zip(cells.into_iter().zip(tiles))
+                        .map(|((rotations, player_id), (cell, tile))| Move {
+                            tile: (0..rotations).fold(tile, |tile, _| tile.rotated(true)),
+                            cell,
+                            player_id,
+                        })
+                        .collect()
+                })
+        }
+
+        /// Player count and the tiles they'll play in turn, each to be placed in front of the acting player's pawn
+        /// like in a real game.
+        fn playthrough() -> impl Strategy<Value = (usize, Vec<Tile>)> {
+            (
+                1..=8usize,
+                Just(all_tiles().to_vec()).prop_shuffle(),
+                prop::collection::vec(0..4usize, TILE_COUNT),
+            )
+                .prop_map(|(player_count, tiles, rotations)| {
+                    let tiles = tiles
+                        .into_iter()
+                        .zip(rotations)
+                        .map(|(tile, rotations)| (0..rotations).fold(tile, |tile, _| tile.rotated(true)))
+                        .collect();
+                    (player_count, tiles)
+                })
+        }
+
+        fn board_from(history: &[Move]) -> Board {
+            let mut board = Board::new();
+            history.iter().for_each(|&mov| board.place_tile(mov));
+            board
+        }
+
+        fn players(count: usize) -> Vec<Player> {
+            let starts = PlayerPos::valid_spawn_positions();
+            (0..count)
+                .map(|id| Player { id, pos: starts[id * 3], alive: true })
+                .collect()
+        }
+
+        proptest! {
+            #[test]
+            fn traversal_ends_off_the_board_or_at_an_empty_cell(history in history()) {
+                let board = board_from(&history);
+
+                for start in PlayerPos::valid_spawn_positions() {
+                    let end = board.traverse_from(start);
+                    if board.get_tile_at(start.cell).is_none() {
+                        prop_assert_eq!(end, start);
+                    } else {
+                        prop_assert_ne!(end, start);
+                        prop_assert!(end.on_edge() || board.get_tile_at(end.cell).is_none());
+                    }
+                }
+            }
+
+            #[test]
+            fn pawns_starting_apart_never_end_up_on_the_same_spot(history in history()) {
+                let board = board_from(&history);
+
+                let mut ends: Vec<PlayerPos> = PlayerPos::valid_spawn_positions().into_iter().map(|start| board.traverse_from(start)).collect();
+                let count = ends.len();
+                ends.sort_by_key(|pos| (pos.cell.row, pos.cell.col, pos.endpoint));
+                ends.dedup();
+                prop_assert_eq!(ends.len(), count);
+            }
+
+            #[test]
+            fn dealing_conserves_tiles(player_count in 1..=8usize) {
+                let game = Game::new(players(player_count));
+
+                let in_hands: usize = game.hands.values().map(Vec::len).sum();
+                prop_assert!(game.hands.values().all(|hand| hand.len() <= game.config().hand_size()));
+                prop_assert_eq!(game.hands.len(), player_count);
+                prop_assert_eq!(game.deck.len() + in_hands, TILE_COUNT);
+            }
+
+            #[test]
+            fn replaying_the_same_moves_gives_the_same_state(history in history(), player_count in 1..=8usize) {
+                let mut a = Game::new(players(player_count));
+                let mut b = Game::new(players(player_count));
+                for &mov in &history {
+                    a.perform_move(mov);
+                    b.perform_move(mov);
+                }
+
+                prop_assert_eq!(&a.board.history, &history);
+                prop_assert_eq!(a.state_hash(), b.state_hash());
+            }
+
+            #[test]
+            fn pawns_stay_consistent_through_a_playthrough((player_count, tiles) in playthrough()) {
+                let starts: Vec<PlayerPos> = players(player_count).iter().map(|player| player.pos).collect();
+                let mut game = Game::new(players(player_count));
+                let mut next_seat = 0;
+
+                for tile in tiles {
+                    let Some(seat) = (0..player_count)
+                        .map(|i| (next_seat + i) % player_count)
+                        .find(|&seat| game.players[seat].alive)
+                    else {
+                        break;
+                    };
+                    next_seat = seat + 1;
+
+                    let before: Vec<(PlayerPos, bool)> = game.players.iter().map(|player| (player.pos, player.alive)).collect();
+                    let cell = before[seat].0.cell;
+                    prop_assert!(game.board.get_tile_at(cell).is_none());
+                    game.perform_move(Move { tile, cell, player_id: game.players[seat].id });
+
+                    // the tile went right in front of the acting player, so they must have moved
+                    prop_assert_ne!(game.players[seat].pos, before[seat].0);
+                    for (i, player) in game.players.iter().enumerate() {
+                        let (pos_before, alive_before) = before[i];
+                        if !alive_before {
+                            prop_assert!(!player.alive);
+                            prop_assert_eq!(player.pos, pos_before);
+                        } else if player.alive {
+                            prop_assert!(!player.pos.on_edge() || player.pos == starts[i]);
+                            prop_assert!(game.board.get_tile_at(player.pos.cell).is_none());
+                        } else {
+                            prop_assert!(player.pos.on_edge());
+                        }
+                    }
+
+                    let mut living: Vec<PlayerPos> = game.players.iter().filter(|player| player.alive).map(|player| player.pos).collect();
+                    let count = living.len();
+                    living.sort_by_key(|pos| (pos.cell.row, pos.cell.col, pos.endpoint));
+                    living.dedup();
+                    prop_assert_eq!(living.len(), count);
+                }
+            }
+
+            #[test]
+            fn notation_round_trips_any_history(history in history()) {
+                let encoded = encode_game(&history).unwrap();
+                prop_assert_eq!(decode_game(&encoded).unwrap(), history);
+            }
+        }
+    }
 }
@@ -0,0 +1,206 @@
+//! # Tsurust notation (TSN)
+//! A compact, human readable way of writing down moves, e.g. `P2 C3 T17R1`:
+//! player 2 places tile #17 (see `catalog`), rotated clockwise once, on row C, column 3.
+//! Rows are lettered from A (top) and columns numbered from 1 (left).
+//! A whole game is written as one move per line; `;` also separates moves when reading.
+
+use std::fmt;
+
+use crate::board::*;
+use crate::catalog::{identify, tile_by_id};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    /// The move doesn't have the three `P.. XN T..R.` parts
+    Malformed(String),
+    InvalidPlayer(String),
+    InvalidCell(String),
+    InvalidTile(String),
+    /// The tile isn't one of the catalog tiles, so it has no number
+    UnknownTile(Tile),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Malformed(mov) => write!(f, "malformed move `{}`", mov),
+            NotationError::InvalidPlayer(player) => write!(f, "invalid player `{}`", player),
+            NotationError::InvalidCell(cell) => write!(f, "invalid cell `{}`", cell),
+            NotationError::InvalidTile(tile) => write!(f, "invalid tile `{}`", tile),
+            NotationError::UnknownTile(tile) => write!(f, "tile {:?} is not in the catalog", tile),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl Move {
+    pub fn to_notation(&self) -> Result<String, NotationError> {
+        let (id, rotations) = identify(&self.tile).ok_or(NotationError::UnknownTile(self.tile))?;
+        let row = (b'A' + self.cell.row as u8) as char;
+
+        Ok(format!("P{} {}{} T{}R{}", self.player_id, row, self.cell.col + 1, id, rotations))
+    }
+
+    pub fn from_notation(notation: &str) -> Result<Move, NotationError> {
+        let parts: Vec<&str> = notation.split_whitespace().collect();
+        let [player, cell, tile] = parts[..] else {
+            return Err(NotationError::Malformed(notation.to_string()));
+        };
+
+        Ok(Move {
+            player_id: parse_player(player)?,
+            cell: parse_cell(cell)?,
+            tile: parse_tile(tile)?,
+        })
+    }
+}
+
+/// Writes down a whole game, one move per line.
+pub fn encode_game(history: &[Move]) -> Result<String, NotationError> {
+    let moves: Vec<String> = history.iter().map(Move::to_notation).collect::<Result<_, _>>()?;
+    Ok(moves.join("\n"))
+}
+
+/// Reads back a game written by `encode_game`. Blank lines are ignored.
+pub fn decode_game(notation: &str) -> Result<Vec<Move>, NotationError> {
+    notation
+        .split(['\n', ';'])
+        .map(str::trim)
+        .filter(|mov| !mov.is_empty())
+        .map(Move::from_notation)
+        .collect()
+}
+
+fn parse_player(player: &str) -> Result<PlayerID, NotationError> {
+    player
+        .strip_prefix(['P', 'p'])
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| NotationError::InvalidPlayer(player.to_string()))
+}
+
+fn parse_cell(cell: &str) -> Result<CellCoord, NotationError> {
+    let invalid = || NotationError::InvalidCell(cell.to_string());
+
+    let mut chars = cell.chars();
+    let row_letter = chars.next().ok_or_else(invalid)?.to_ascii_uppercase();
+    let col_number: usize = chars.as_str().parse().map_err(|_| invalid())?;
+
+    if !row_letter.is_ascii_uppercase() {
+        return Err(invalid());
+    }
+    let row = (row_letter as u8 - b'A') as usize;
+    let col = col_number.checked_sub(1).ok_or_else(invalid)?;
+
+    if row > MAX || col > MAX {
+        return Err(invalid());
+    }
+    Ok(CellCoord { row, col })
+}
+
+fn parse_tile(tile: &str) -> Result<Tile, NotationError> {
+    let invalid = || NotationError::InvalidTile(tile.to_string());
+
+    let spec = tile.strip_prefix(['T', 't']).ok_or_else(invalid)?;
+    // the rotation is optional, `T17` is the same as `T17R0`
+    let (id, rotations) = match spec.split_once(['R', 'r']) {
+        Some((id, rotations)) => (id, rotations.parse::<usize>().map_err(|_| invalid())?),
+        None => (spec, 0),
+    };
+    let id = id.parse().map_err(|_| invalid())?;
+
+    if rotations > 3 {
+        return Err(invalid());
+    }
+    let canonical = tile_by_id(id).ok_or_else(invalid)?;
+    Ok((0..rotations).fold(canonical, |tile, _| tile.rotated(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::*;
+    use crate::catalog::*;
+    use crate::notation::*;
+
+    #[test]
+    fn reads_the_documented_example() {
+        let mov = Move::from_notation("P2 C3 T17R1").unwrap();
+
+        assert_eq!(mov.player_id, 2);
+        assert_eq!(mov.cell, CellCoord { row: 2, col: 2 });
+        assert_eq!(mov.tile, tile_by_id(17).unwrap().rotated(true));
+    }
+
+    #[test]
+    fn every_tile_and_rotation_round_trips() {
+        for (i, &tile) in all_tiles().iter().enumerate() {
+            let mut rotated = tile;
+            for _ in 0..4 {
+                let mov = Move { tile: rotated, cell: CellCoord { row: i % 6, col: 5 - i % 6 }, player_id: i };
+                let notation = mov.to_notation().unwrap();
+
+                assert_eq!(Move::from_notation(&notation), Ok(mov), "{}", notation);
+                rotated = rotated.rotated(true);
+            }
+        }
+    }
+
+    #[test]
+    fn corners_are_written_as_expected() {
+        let tile = tile_by_id(1).unwrap();
+
+        let top_left = Move { tile, cell: CellCoord { row: 0, col: 0 }, player_id: 1 };
+        let bottom_right = Move { tile, cell: CellCoord { row: 5, col: 5 }, player_id: 1 };
+
+        assert_eq!(top_left.to_notation().unwrap(), "P1 A1 T1R0");
+        assert_eq!(bottom_right.to_notation().unwrap(), "P1 F6 T1R0");
+    }
+
+    #[test]
+    fn parsing_is_lenient_about_case_and_missing_rotation() {
+        assert_eq!(Move::from_notation("p2 c3 t17"), Move::from_notation("P2 C3 T17R0"));
+    }
+
+    #[test]
+    fn rejects_invalid_moves() {
+        assert!(matches!(Move::from_notation("P2 C3"), Err(NotationError::Malformed(_))));
+        assert!(matches!(Move::from_notation("X2 C3 T17R1"), Err(NotationError::InvalidPlayer(_))));
+        assert!(matches!(Move::from_notation("P2 G1 T17R1"), Err(NotationError::InvalidCell(_))));
+        assert!(matches!(Move::from_notation("P2 A7 T17R1"), Err(NotationError::InvalidCell(_))));
+        assert!(matches!(Move::from_notation("P2 A0 T17R1"), Err(NotationError::InvalidCell(_))));
+        assert!(matches!(Move::from_notation("P2 C3 T36R0"), Err(NotationError::InvalidTile(_))));
+        assert!(matches!(Move::from_notation("P2 C3 T17R4"), Err(NotationError::InvalidTile(_))));
+    }
+
+    #[test]
+    fn tiles_outside_the_catalog_cant_be_written() {
+        // every entry connects to itself: not a real tile
+        let tile = Tile { segments: [seg(0, 0), seg(1, 1), seg(2, 2), seg(3, 3)] };
+        let mov = Move { tile, cell: CellCoord { row: 0, col: 0 }, player_id: 1 };
+
+        assert_eq!(mov.to_notation(), Err(NotationError::UnknownTile(tile)));
+    }
+
+    #[test]
+    fn whole_games_round_trip() {
+        let history: Vec<Move> = all_tiles()
+            .iter()
+            .enumerate()
+            .map(|(i, &tile)| Move {
+                tile: tile.rotated(i % 2 == 0),
+                cell: CellCoord { row: i / 6, col: i % 6 },
+                player_id: i % 3,
+            })
+            .collect();
+
+        let encoded = encode_game(&history).unwrap();
+        assert_eq!(encoded.lines().count(), history.len());
+        assert_eq!(decode_game(&encoded).unwrap(), history);
+    }
+
+    #[test]
+    fn games_can_be_separated_by_semicolons() {
+        let moves = decode_game("P1 A1 T1R0; P2 F6 T35R2;\n\n").unwrap();
+        assert_eq!(moves.len(), 2);
+    }
+}
@@ -0,0 +1,292 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::Parser;
+use serde::Deserialize;
+
+/// Command line flags. Anything given here overrides the config file.
+#[derive(Parser, Debug)]
+#[command(about = "Tsurust game server")]
+struct Args {
+    /// Path to a TOML config file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// Address to bind to
+    #[arg(long)]
+    address: Option<IpAddr>,
+    /// Port to listen on
+    #[arg(short, long)]
+    port: Option<u16>,
+    /// Seconds between heartbeat pings
+    #[arg(long)]
+    ping_interval_secs: Option<u64>,
+    /// Seconds of silence after which a client is disconnected
+    #[arg(long)]
+    pong_timeout_secs: Option<u64>,
+    /// PEM certificate chain; serves wss:// when given together with --tls-key
+    #[arg(long)]
+    tls_cert: Option<PathBuf>,
+    /// PEM private key for --tls-cert
+    #[arg(long)]
+    tls_key: Option<PathBuf>,
+    /// Directory with the built web client (client-egui/docs) to serve over HTTP
+    #[arg(long)]
+    web_root: Option<PathBuf>,
+    /// Serve Prometheus metrics on /metrics
+    #[arg(long)]
+    metrics: bool,
+    /// Most clients connected at once; more are turned away with 503 Service Unavailable
+    #[arg(long)]
+    max_connections: Option<u64>,
+    /// Largest message a client may send, in bytes
+    #[arg(long)]
+    max_message_bytes: Option<usize>,
+    /// Messages per second a client may send on average
+    #[arg(long)]
+    rate_limit_per_sec: Option<u32>,
+    /// Messages a client may send in a burst before being rate limited
+    #[arg(long)]
+    rate_limit_burst: Option<u32>,
+    /// Log filter, e.g. `info` or `tsurust_server=debug`. RUST_LOG takes precedence when set
+    #[arg(long)]
+    log_level: Option<String>,
+    /// Write logs as JSON lines instead of human readable text
+    #[arg(long)]
+    log_json: bool,
+}
+
+/// Server settings, read from an optional TOML file and then the command line.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub address: IpAddr,
+    pub port: u16,
+    pub ping_interval_secs: u64,
+    pub pong_timeout_secs: u64,
+    pub tls_cert: Option<PathBuf>,
+    pub tls_key: Option<PathBuf>,
+    pub web_root: Option<PathBuf>,
+    pub metrics: bool,
+    pub max_connections: u64,
+    pub max_message_bytes: usize,
+    pub rate_limit_per_sec: u32,
+    pub rate_limit_burst: u32,
+    pub log_level: String,
+    pub log_json: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: 3030,
+            ping_interval_secs: 10,
+            pong_timeout_secs: 30,
+            tls_cert: None,
+            tls_key: None,
+            web_root: None,
+            metrics: false,
+            max_connections: 1000,
+            max_message_bytes: 64 * 1024,
+            rate_limit_per_sec: 10,
+            rate_limit_burst: 20,
+            log_level: "info".to_string(),
+            log_json: false,
+        }
+    }
+}
+
+impl Config {
+    /// Builds the config from the process arguments, loading the config file if one was given.
+    pub fn load() -> Result<Config, String> {
+        Config::from_args(Args::parse())
+    }
+
+    fn from_args(args: Args) -> Result<Config, String> {
+        let mut config = match &args.config {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path)
+                    .map_err(|e| format!("can't read config file {}: {}", path.display(), e))?;
+                Config::from_toml(&contents)
+                    .map_err(|e| format!("invalid config file {}: {}", path.display(), e))?
+            }
+            None => Config::default(),
+        };
+
+        config.override_with(args);
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parses a config file. Settings it leaves out keep their defaults.
+    fn from_toml(contents: &str) -> Result<Config, String> {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    }
+
+    /// Applies the command line flags on top of whatever the file said.
+    fn override_with(&mut self, args: Args) {
+        let config = self;
+        if let Some(address) = args.address {
+            config.address = address;
+        }
+        if let Some(port) = args.port {
+            config.port = port;
+        }
+        if let Some(secs) = args.ping_interval_secs {
+            config.ping_interval_secs = secs;
+        }
+        if let Some(secs) = args.pong_timeout_secs {
+            config.pong_timeout_secs = secs;
+        }
+        if let Some(path) = args.tls_cert {
+            config.tls_cert = Some(path);
+        }
+        if let Some(path) = args.tls_key {
+            config.tls_key = Some(path);
+        }
+        if let Some(path) = args.web_root {
+            config.web_root = Some(path);
+        }
+        if args.metrics {
+            config.metrics = true;
+        }
+        if let Some(max) = args.max_connections {
+            config.max_connections = max;
+        }
+        if let Some(bytes) = args.max_message_bytes {
+            config.max_message_bytes = bytes;
+        }
+        if let Some(per_sec) = args.rate_limit_per_sec {
+            config.rate_limit_per_sec = per_sec;
+        }
+        if let Some(burst) = args.rate_limit_burst {
+            config.rate_limit_burst = burst;
+        }
+        if let Some(level) = args.log_level {
+            config.log_level = level;
+        }
+        if args.log_json {
+            config.log_json = true;
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.ping_interval_secs == 0 {
+            return Err("ping_interval_secs must be greater than 0".to_string());
+        }
+        if self.pong_timeout_secs <= self.ping_interval_secs {
+            return Err("pong_timeout_secs must be greater than ping_interval_secs".to_string());
+        }
+        if self.max_connections == 0 {
+            return Err("max_connections must be greater than 0".to_string());
+        }
+        if self.max_message_bytes == 0 {
+            return Err("max_message_bytes must be greater than 0".to_string());
+        }
+        if self.rate_limit_per_sec == 0 || self.rate_limit_burst == 0 {
+            return Err("rate_limit_per_sec and rate_limit_burst must be greater than 0".to_string());
+        }
+        match (&self.tls_cert, &self.tls_key) {
+            (Some(cert), Some(key)) => {
+                for path in [cert, key] {
+                    if !path.is_file() {
+                        return Err(format!("TLS file {} does not exist", path.display()));
+                    }
+                }
+            }
+            (None, None) => {}
+            _ => return Err("tls_cert and tls_key must be given together".to_string()),
+        }
+        if let Some(dir) = &self.web_root {
+            if !dir.join("index.html").is_file() {
+                return Err(format!("web root {} has no index.html, build the web client first", dir.display()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn bind_address(&self) -> SocketAddr {
+        SocketAddr::new(self.address, self.port)
+    }
+
+    /// The certificate and key paths, if the server should terminate TLS itself.
+    pub fn tls(&self) -> Option<(&Path, &Path)> {
+        match (&self.tls_cert, &self.tls_key) {
+            (Some(cert), Some(key)) => Some((cert, key)),
+            _ => None,
+        }
+    }
+
+    pub fn ping_interval(&self) -> Duration {
+        Duration::from_secs(self.ping_interval_secs)
+    }
+
+    pub fn pong_timeout(&self) -> Duration {
+        Duration::from_secs(self.pong_timeout_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::{Args, Config};
+
+    fn args(flags: &[&str]) -> Args {
+        Args::try_parse_from(std::iter::once("tsurust-server").chain(flags.iter().copied())).unwrap()
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let error = Config::from_toml("prot = 4000").unwrap_err();
+        assert!(error.contains("unknown field"), "{}", error);
+    }
+
+    #[test]
+    fn missing_keys_keep_their_defaults() {
+        let config = Config::from_toml("port = 4000").unwrap();
+
+        assert_eq!(config.port, 4000);
+        assert_eq!(config.ping_interval_secs, Config::default().ping_interval_secs);
+    }
+
+    #[test]
+    fn flags_override_the_file() {
+        let mut config = Config::from_toml("port = 4000\nping_interval_secs = 5").unwrap();
+        config.override_with(args(&["--port", "5000"]));
+
+        assert_eq!(config.port, 5000);
+        assert_eq!(config.ping_interval_secs, 5);
+    }
+
+    #[test]
+    fn config_file_is_read_from_the_given_path() {
+        let path = std::env::temp_dir().join(format!("tsurust-config-test-{}.toml", std::process::id()));
+        std::fs::write(&path, "port = 4000\nmetrics = true").unwrap();
+
+        let config = Config::from_args(args(&["--config", path.to_str().unwrap(), "--port", "5000"]));
+        std::fs::remove_file(&path).unwrap();
+
+        let config = config.unwrap();
+        assert_eq!(config.port, 5000);
+        assert!(config.metrics);
+    }
+
+    #[test]
+    fn pong_timeout_must_be_longer_than_the_ping_interval() {
+        let config = Config::from_toml("ping_interval_secs = 30\npong_timeout_secs = 30").unwrap();
+        assert!(config.validate().is_err());
+
+        let config = Config::from_toml("ping_interval_secs = 0").unwrap();
+        assert!(config.validate().is_err());
+
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn tls_paths_must_be_given_together() {
+        let config = Config::from_toml("tls_cert = \"cert.pem\"").unwrap();
+        assert!(config.validate().unwrap_err().contains("together"));
+    }
+}
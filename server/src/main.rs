@@ -1,52 +1,207 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use futures::stream::SplitStream;
-use futures::StreamExt;
+use std::time::Instant;
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
 use tokio::sync::{mpsc, RwLock};
-use tokio::sync::mpsc::Sender;
-use warp::Filter;
+use tracing::{debug, error, info, info_span, warn, Instrument};
+use tracing_subscriber::EnvFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
 use warp::ws::{Message, WebSocket};
 use tsurust_common::board::PlayerID;
 
+use crate::config::Config;
+use crate::metrics::Metrics;
+use crate::rate_limit::RateLimiter;
+
+mod config;
+mod metrics;
+mod rate_limit;
+
 type Clients = Arc<RwLock<HashMap<PlayerID, mpsc::UnboundedSender<Message>>>>;
 
+/// Tags every connection's log lines, so one client's session can be followed through the log
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Websocket close code for clients that broke the rules, e.g. the rate limit
+const POLICY_VIOLATION: u16 = 1008;
+/// Websocket close code for messages over `max_message_bytes`
+const MESSAGE_TOO_BIG: u16 = 1009;
 
 #[tokio::main]
 async fn main() {
-    let clients = Clients::default();
-
-    eprintln!("hapi");
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = init_logging(&config) {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+    let config = Arc::new(config);
+    let metrics = Arc::new(Metrics::default());
+    let _clients = Clients::default();
 
+    info!(address = %config.bind_address(), tls = config.tls().is_some(), "starting server");
 
     let ws_route = warp::path("tsurust-ws")
         .and(warp::ws())
         //.and(warp::any().map(move || clients.clone()))
-        .map(move |ws: warp::ws::Ws| {
-            ws.on_upgrade(|socket| client_connected(socket))
+        .map({
+            let config = config.clone();
+            let metrics = metrics.clone();
+            move |ws: warp::ws::Ws| {
+                // checked before the upgrade, so a burst of simultaneous connections can go slightly over
+                if metrics.active_connections() >= config.max_connections {
+                    warn!(max_connections = config.max_connections, "server is full, turning a client away");
+                    return warp::reply::with_status("server is full, try again later", StatusCode::SERVICE_UNAVAILABLE)
+                        .into_response();
+                }
+
+                let config = config.clone();
+                let metrics = metrics.clone();
+                let connection_id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+                // tungstenite refuses anything bigger, see `is_message_too_big`
+                let ws = ws.max_message_size(config.max_message_bytes).max_frame_size(config.max_message_bytes);
+                ws.on_upgrade(move |socket| {
+                    client_connected(socket, config, metrics).instrument(info_span!("connection", connection_id))
+                })
+                .into_response()
+            }
         });
 
-    warp::serve(ws_route)
-        .run(([127, 0, 0, 1], 3030)).await;
+    let ws_route = if config.metrics {
+        let metrics_route = warp::path("metrics")
+            .and(warp::path::end())
+            .and(warp::get())
+            .map(move || warp::reply::with_header(metrics.render(), "content-type", "text/plain; version=0.0.4"));
+        ws_route
+            .or(metrics_route)
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed()
+    } else {
+        ws_route
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed()
+    };
+
+    // When a web root is configured, anything that isn't the websocket endpoint is served from it,
+    // so the same address hosts both the wasm client and the game
+    let routes = match config.web_root.clone() {
+        Some(dir) => ws_route
+            .or(warp::fs::dir(dir))
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed(),
+        None => ws_route,
+    };
+
+    let server = warp::serve(routes);
+    match config.tls() {
+        Some((cert, key)) => server.tls().cert_path(cert).key_path(key).run(config.bind_address()).await,
+        None => server.run(config.bind_address()).await,
+    }
+}
+
+fn init_logging(config: &Config) -> Result<(), String> {
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) => EnvFilter::try_new(&config.log_level)
+            .map_err(|e| format!("invalid log_level `{}`: {}", config.log_level, e))?,
+    };
+
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    if config.log_json {
+        builder.json().init();
+    } else {
+        builder.init();
+    }
+    Ok(())
 }
 
-async fn client_connected(ws: WebSocket) {
-    let (ws_out, mut ws_in) = ws.split(); //maybe spawn an outbound task?
-    eprintln!("clioent connected");
+async fn client_connected(ws: WebSocket, config: Arc<Config>, metrics: Arc<Metrics>) {
+    let (ws_out, ws_in) = ws.split(); //maybe spawn an outbound task?
+    info!("client connected");
+    metrics.client_connected();
 
-    client_message_loop(ws_in).await;
+    client_message_loop(ws_out, ws_in, config, &metrics).await;
 
-    dbg!("player disconnected");
+    metrics.client_disconnected();
+
+    info!("client disconnected");
 }
 
-async fn client_message_loop(mut ws_in: SplitStream<WebSocket>) {
-    while let Some(result) = ws_in.next().await { //and_then()?
-        match result {
-            Ok(msg) => dbg!(msg),
-            Err(e) => {
-                eprintln!("websocket error: {}", e);
-                break;
+async fn client_message_loop(
+    mut ws_out: SplitSink<WebSocket, Message>,
+    mut ws_in: SplitStream<WebSocket>,
+    config: Arc<Config>,
+    metrics: &Metrics,
+) {
+    // Ping every client periodically; one that hasn't answered (or sent anything) in a while is considered dead
+    let mut heartbeat = tokio::time::interval(config.ping_interval());
+    let mut last_seen = Instant::now();
+    let mut ping_sent: Option<Instant> = None;
+    let mut rate_limiter = RateLimiter::new(config.rate_limit_per_sec, config.rate_limit_burst, last_seen);
+
+    loop {
+        tokio::select! {
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > config.pong_timeout() {
+                    warn!(silent_for = ?last_seen.elapsed(), "client unresponsive, closing connection");
+                    metrics.heartbeat_timeout();
+                    let _ = ws_out.send(Message::close()).await;
+                    break;
+                }
+                if let Err(e) = ws_out.send(Message::ping(Vec::new())).await {
+                    error!(error = %e, "websocket error");
+                    metrics.websocket_error();
+                    break;
+                }
+                ping_sent = Some(Instant::now());
             }
-        };
+            result = ws_in.next() => {
+                let Some(result) = result else { break };
+                match result {
+                    Ok(msg) => {
+                        last_seen = Instant::now();
+                        if msg.is_pong() {
+                            if let Some(sent) = ping_sent.take() {
+                                debug!(rtt_ms = sent.elapsed().as_millis() as u64, "pong");
+                                metrics.round_trip(sent.elapsed());
+                            }
+                        } else if !rate_limiter.allow(last_seen) {
+                            warn!("client is sending too many messages, closing connection");
+                            metrics.rate_limited();
+                            let _ = ws_out.send(Message::close_with(POLICY_VIOLATION, "rate limit exceeded")).await;
+                            break;
+                        } else {
+                            metrics.message_received();
+                            debug!(?msg, "message received");
+                        }
+                    }
+                    Err(e) if is_message_too_big(&e) => {
+                        warn!(error = %e, "client sent an oversized message, closing connection");
+                        metrics.oversized_message();
+                        let _ = ws_out.send(Message::close_with(MESSAGE_TOO_BIG, "message too big")).await;
+                        break;
+                    }
+                    Err(e) => {
+                        error!(error = %e, "websocket error");
+                        metrics.websocket_error();
+                        break;
+                    }
+                };
+            }
+        }
     }
 }
 
+/// warp only hands out an opaque error, but keeps tungstenite's as its source.
+fn is_message_too_big(error: &warp::Error) -> bool {
+    let source = std::error::Error::source(error).and_then(|source| source.downcast_ref::<tungstenite::Error>());
+    matches!(source, Some(tungstenite::Error::Capacity(_)))
+}
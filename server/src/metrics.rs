@@ -0,0 +1,145 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds of the heartbeat round trip histogram buckets, in milliseconds
+const RTT_BUCKETS_MS: [u64; 9] = [1, 5, 10, 25, 50, 100, 250, 500, 1000];
+
+/// Server wide counters, rendered in the Prometheus text format on `/metrics`.
+#[derive(Default)]
+pub struct Metrics {
+    connections_active: AtomicU64,
+    connections_total: AtomicU64,
+    messages_received: AtomicU64,
+    websocket_errors: AtomicU64,
+    heartbeat_timeouts: AtomicU64,
+    rate_limited: AtomicU64,
+    oversized_messages: AtomicU64,
+    // not cumulative: each round trip is counted in the first bucket it fits in, the last one is +Inf
+    rtt_buckets: [AtomicU64; RTT_BUCKETS_MS.len() + 1],
+    rtt_sum_ms: AtomicU64,
+}
+
+impl Metrics {
+    pub fn client_connected(&self) {
+        self.connections_active.fetch_add(1, Ordering::Relaxed);
+        self.connections_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn client_disconnected(&self) {
+        self.connections_active.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn active_connections(&self) -> u64 {
+        self.connections_active.load(Ordering::Relaxed)
+    }
+
+    pub fn message_received(&self) {
+        self.messages_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn websocket_error(&self) {
+        self.websocket_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn heartbeat_timeout(&self) {
+        self.heartbeat_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn rate_limited(&self) {
+        self.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn oversized_message(&self) {
+        self.oversized_messages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn round_trip(&self, rtt: Duration) {
+        let ms = rtt.as_millis() as u64;
+        let bucket = RTT_BUCKETS_MS.iter().position(|&bound| ms <= bound).unwrap_or(RTT_BUCKETS_MS.len());
+        self.rtt_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.rtt_sum_ms.fetch_add(ms, Ordering::Relaxed);
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: &AtomicU64| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+        };
+
+        metric("tsurust_connections_active", "gauge", "Currently open websocket connections", &self.connections_active);
+        metric("tsurust_connections_total", "counter", "Websocket connections accepted", &self.connections_total);
+        metric("tsurust_messages_received_total", "counter", "Non heartbeat messages received from clients", &self.messages_received);
+        metric("tsurust_websocket_errors_total", "counter", "Connections dropped because of a websocket error", &self.websocket_errors);
+        metric("tsurust_heartbeat_timeouts_total", "counter", "Connections closed for not answering pings", &self.heartbeat_timeouts);
+        metric("tsurust_rate_limited_total", "counter", "Connections closed for sending too many messages", &self.rate_limited);
+        metric("tsurust_oversized_messages_total", "counter", "Connections closed for sending a message over max_message_bytes", &self.oversized_messages);
+
+        let name = "tsurust_heartbeat_rtt_milliseconds";
+        let _ = writeln!(out, "# HELP {} Round trip time of heartbeat pings", name);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        let mut count = 0;
+        for (i, bucket) in self.rtt_buckets.iter().enumerate() {
+            count += bucket.load(Ordering::Relaxed);
+            let bound = RTT_BUCKETS_MS.get(i).map_or("+Inf".to_string(), u64::to_string);
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, count);
+        }
+        let _ = writeln!(out, "{}_sum {}", name, self.rtt_sum_ms.load(Ordering::Relaxed));
+        let _ = writeln!(out, "{}_count {}", name, count);
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Metrics;
+
+    #[test]
+    fn round_trips_render_as_a_cumulative_histogram() {
+        let metrics = Metrics::default();
+        for ms in [0, 7, 300, 1500] {
+            metrics.round_trip(Duration::from_millis(ms));
+        }
+
+        let rendered = metrics.render();
+        let histogram: Vec<&str> = rendered
+            .lines()
+            .filter(|line| line.starts_with("tsurust_heartbeat_rtt_milliseconds"))
+            .collect();
+
+        assert_eq!(histogram, [
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"1\"} 1",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"5\"} 1",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"10\"} 2",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"25\"} 2",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"50\"} 2",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"100\"} 2",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"250\"} 2",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"500\"} 3",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"1000\"} 3",
+            "tsurust_heartbeat_rtt_milliseconds_bucket{le=\"+Inf\"} 4",
+            "tsurust_heartbeat_rtt_milliseconds_sum 1807",
+            "tsurust_heartbeat_rtt_milliseconds_count 4",
+        ]);
+    }
+
+    #[test]
+    fn counters_render_their_current_value() {
+        let metrics = Metrics::default();
+        metrics.client_connected();
+        metrics.client_connected();
+        metrics.client_disconnected();
+        metrics.oversized_message();
+
+        let rendered = metrics.render();
+        assert!(rendered.lines().any(|line| line == "tsurust_connections_active 1"));
+        assert!(rendered.lines().any(|line| line == "tsurust_connections_total 2"));
+        assert!(rendered.lines().any(|line| line == "tsurust_oversized_messages_total 1"));
+        assert!(rendered.lines().any(|line| line == "tsurust_websocket_errors_total 0"));
+    }
+}
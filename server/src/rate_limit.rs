@@ -0,0 +1,74 @@
+use std::time::Instant;
+
+/// Token bucket: a client may send `burst` messages at once, refilled at `per_sec` messages per second.
+pub struct RateLimiter {
+    per_sec: f64,
+    burst: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Starts with a full bucket at `now`.
+    pub fn new(per_sec: u32, burst: u32, now: Instant) -> Self {
+        RateLimiter {
+            per_sec: per_sec as f64,
+            burst: burst as f64,
+            tokens: burst as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Takes a token for a message received at `now`, returns false if the client is over its limit.
+    pub fn allow(&mut self, now: Instant) -> bool {
+        let refill = now.duration_since(self.last_refill).as_secs_f64() * self.per_sec;
+        self.tokens = (self.tokens + refill).min(self.burst);
+        self.last_refill = now;
+
+        if self.tokens >= 1. {
+            self.tokens -= 1.;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::RateLimiter;
+
+    #[test]
+    fn burst_is_allowed_then_exhausted() {
+        let now = Instant::now();
+        let mut limiter = RateLimiter::new(10, 5, now);
+
+        assert!((0..5).all(|_| limiter.allow(now)));
+        assert!(!limiter.allow(now));
+    }
+
+    #[test]
+    fn tokens_refill_at_per_sec() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(10, 5, start);
+        (0..5).for_each(|_| assert!(limiter.allow(start)));
+
+        // 10 per second is one token every 100ms
+        let later = start + Duration::from_millis(250);
+        assert!(limiter.allow(later));
+        assert!(limiter.allow(later));
+        assert!(!limiter.allow(later));
+    }
+
+    #[test]
+    fn tokens_are_capped_at_burst() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(10, 5, start);
+
+        let much_later = start + Duration::from_secs(60);
+        assert!((0..5).all(|_| limiter.allow(much_later)));
+        assert!(!limiter.allow(much_later));
+    }
+}